@@ -0,0 +1,39 @@
+use std::{env, fs, path::Path};
+
+/// Generates one test per `example_input/*.expected` file, see `src/examples.rs`.
+fn main() {
+    println!("cargo:rerun-if-changed=example_input");
+
+    let mut names: Vec<String> = fs::read_dir("example_input")
+        .expect("read example_input")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "expected" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    names.sort();
+
+    let mut tests = String::new();
+
+    for name in names {
+        let content =
+            fs::read_to_string(Path::new("example_input").join(format!("{}.expected", name)))
+                .expect("read expected answers");
+
+        if let Some(reason) = content
+            .lines()
+            .find_map(|line| line.strip_prefix("ignore:"))
+        {
+            tests += &format!("#[ignore = {:?}]\n", reason.trim());
+        }
+
+        tests += &format!("#[test]\nfn {0}() {{\n    check({0:?});\n}}\n\n", name);
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("examples.rs"), tests).expect("write example tests");
+}
@@ -0,0 +1,140 @@
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Wide enough for every integer type the solutions return, signed or not.
+    Integer(i128),
+    Text(String),
+    /// Pixels that spell out the answer, like the CRT of day 10. `true` is a lit pixel.
+    Bitmap(Vec<Vec<bool>>),
+}
+
+impl Answer {
+    /// Reads a bitmap drawn with `#` for lit and `.` for dark pixels.
+    pub fn bitmap(drawing: &str) -> Self {
+        Answer::Bitmap(
+            drawing
+                .lines()
+                .map(|line| line.chars().map(|pixel| pixel == '#').collect())
+                .collect(),
+        )
+    }
+
+    /// Reads an answer back from its [`Display`] output, e.g. from the answers file.
+    pub fn parse(value: &str) -> Self {
+        if let Ok(integer) = value.parse::<i128>() {
+            // Keep text like "007" as it was.
+            if integer.to_string() == value {
+                return Answer::Integer(integer);
+            }
+        }
+
+        if value.contains('\n') && value.chars().all(|char| "#.\n".contains(char)) {
+            return Answer::bitmap(value);
+        }
+
+        Answer::Text(value.to_string())
+    }
+
+    /// Whether this is the `expected` answer. Answers read back with [`Answer::parse`] can only
+    /// guess their variant, e.g. `Text("123")` comes back as an integer, so answers of
+    /// different variants are compared by their text.
+    pub fn matches(&self, expected: &Answer) -> bool {
+        match (self, expected) {
+            (Answer::Integer(_), Answer::Integer(_))
+            | (Answer::Text(_), Answer::Text(_))
+            | (Answer::Bitmap(_), Answer::Bitmap(_)) => self == expected,
+            _ => self.to_string() == expected.to_string(),
+        }
+    }
+
+    /// Whether the answer needs lines of its own instead of a table cell.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Bitmap(rows) if rows.len() > 1)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{}", integer),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Bitmap(rows) => {
+                for (index, row) in rows.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+
+                    for lit in row {
+                        f.write_str(if *lit { "#" } else { "." })?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(value: $type) -> Self {
+                    // Lossless, no integer type below is wider than 64 bits.
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[test]
+fn parses_displayed_answers() {
+    let crt = Answer::bitmap("#..#\n.##.");
+
+    assert_eq!(
+        crt,
+        Answer::Bitmap(vec![
+            vec![true, false, false, true],
+            vec![false, true, true, false]
+        ])
+    );
+    assert!(crt.is_multiline());
+
+    for answer in [
+        Answer::from(24000u64),
+        Answer::from(-3i64),
+        Answer::from("CMZ"),
+        Answer::from("007"),
+        crt,
+    ] {
+        assert_eq!(Answer::parse(&answer.to_string()), answer);
+    }
+
+    assert!(!Answer::from("#.#").is_multiline());
+}
+
+#[test]
+fn matches_answers_of_guessed_variants() {
+    assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    assert!(Answer::from("123").matches(&Answer::parse("123")));
+    assert!(Answer::from("#\n.").matches(&Answer::bitmap("#\n.")));
+    assert!(!Answer::from("0123").matches(&Answer::from(123u32)));
+    assert!(!Answer::from(12u32).matches(&Answer::from(13u32)));
+}
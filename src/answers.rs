@@ -0,0 +1,130 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use crate::{
+    answer::Answer,
+    runner::{SolveResult, Status},
+};
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    pub entries: BTreeMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Answers::try_from(content.as_str())
+                .map_err(|err| format!("{}: {}", path.display(), err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.entries.get(&(day, part))
+    }
+
+    pub fn verify(&self, result: &mut SolveResult) {
+        if !matches!(result.status, Status::Unknown | Status::Cached) {
+            return;
+        }
+
+        if let Some(expected) = self.get(result.day, result.part) {
+            result.status = match result
+                .answer
+                .as_ref()
+                .is_some_and(|answer| answer.matches(expected))
+            {
+                true if result.status == Status::Cached => Status::Cached,
+                true => Status::Pass,
+                false => Status::Fail(expected.clone()),
+            };
+        }
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut answers = Answers::default();
+
+        for (number, line) in value.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parsed = line.split_once(": ").and_then(|(key, answer)| {
+                let (day, part) = key.strip_prefix("day")?.split_once("pt")?;
+                Some((
+                    day.parse().ok()?,
+                    part.parse().ok()?,
+                    Answer::parse(unescape(answer).trim_end()),
+                ))
+            });
+
+            let Some((day, part, answer)) = parsed else {
+                return Err(format!("line {}: expected 'dayNptM: answer'", number + 1));
+            };
+
+            answers.entries.insert((day, part), answer);
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), answer) in &self.entries {
+            writeln!(f, "day{}pt{}: {}", day, part, escape(&answer.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+pub fn unescape(value: &str) -> String {
+    let mut output = String::new();
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            output.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+
+    output
+}
+
+#[test]
+fn answers_round_trip() {
+    let content = "# comment\nday1pt1: 24000\n\nday10pt2: #..#\\n.##.\n";
+    let answers = Answers::try_from(content).unwrap();
+
+    assert_eq!(answers.get(1, 1), Some(&Answer::Integer(24000)));
+    assert_eq!(answers.get(10, 2), Some(&Answer::bitmap("#..#\n.##.")));
+    assert_eq!(answers.get(1, 2), None);
+    assert_eq!(
+        answers.to_string(),
+        "day1pt1: 24000\nday10pt2: #..#\\n.##.\n"
+    );
+    assert!(Answers::try_from("day1: 5").is_err());
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    bench::BenchResult,
+    json::Value,
+    report::json_string,
+    runner::{Run, Status},
+};
+
+pub const BASELINE_DIR: &str = "baselines";
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Part number the parse time of a day is kept under, parsing is shared by the parts so it
+/// is timed once per day.
+pub const PARSE_PART: u8 = 0;
+
+/// Changes smaller than this are timer noise, whatever the percentage says.
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    pub name: String,
+    pub timings: BTreeMap<(u8, u8), Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub before: Option<Duration>,
+    pub after: Duration,
+    pub regression: bool,
+}
+
+impl Comparison {
+    /// Percentage change from the baseline, positive when slower.
+    pub fn change(&self) -> Option<f64> {
+        let before = self.before?.as_secs_f64();
+
+        if before == 0.0 {
+            return None;
+        }
+
+        Some((self.after.as_secs_f64() - before) / before * 100.0)
+    }
+}
+
+impl Baseline {
+    /// A bare name refers to `baselines/<name>.json`, anything that looks like a path is used as is.
+    pub fn path(name: &str) -> PathBuf {
+        if name.ends_with(".json") || name.contains(['/', '\\']) {
+            PathBuf::from(name)
+        } else {
+            Path::new(BASELINE_DIR).join(format!("{}.json", name))
+        }
+    }
+
+    pub fn from_run(name: &str, run: &Run) -> Self {
+        let parsed = run
+            .results
+            .iter()
+            .filter(|result| !result.status.is_error() && result.status != Status::Cached);
+
+        let parse_times = parsed
+            .clone()
+            .map(|result| ((result.day, PARSE_PART), result.parse_time));
+
+        let solve_times = parsed
+            .filter(|result| result.status != Status::Todo)
+            .map(|result| ((result.day, result.part), result.solve_time));
+
+        Self {
+            name: name.to_string(),
+            timings: parse_times.chain(solve_times).collect(),
+        }
+    }
+
+    pub fn from_bench(name: &str, results: &[BenchResult]) -> Self {
+        Self {
+            name: name.to_string(),
+            timings: results
+                .iter()
+                .filter_map(|result| {
+                    let stats = result.stats.as_ref().ok()?;
+                    Some(((result.day, result.part), stats.median))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn load(name: &str) -> Result<Self, String> {
+        let path = Baseline::path(name);
+
+        fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| Baseline::try_from(content.as_str()))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = Baseline::path(&self.name);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("{}: {}", parent.display(), err))?;
+        }
+
+        fs::write(&path, self.to_json()).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        Ok(path)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut output = String::new();
+
+        writeln!(output, "{{").unwrap();
+        writeln!(output, "  \"name\": {},", json_string(&self.name)).unwrap();
+        writeln!(output, "  \"timings\": [").unwrap();
+
+        for (index, ((day, part), time)) in self.timings.iter().enumerate() {
+            let separator = if index + 1 < self.timings.len() {
+                ","
+            } else {
+                ""
+            };
+
+            writeln!(
+                output,
+                "    {{\"day\": {}, \"part\": {}, \"time_ns\": {}}}{}",
+                day,
+                part,
+                time.as_nanos(),
+                separator
+            )
+            .unwrap();
+        }
+
+        writeln!(output, "  ]").unwrap();
+        writeln!(output, "}}").unwrap();
+
+        output
+    }
+
+    /// Compares `current` against this baseline, flagging slowdowns of more than `threshold` percent.
+    pub fn compare(&self, current: &Baseline, threshold: f64) -> Vec<Comparison> {
+        current
+            .timings
+            .iter()
+            .map(|(&(day, part), &after)| {
+                let before = self.timings.get(&(day, part)).copied();
+
+                let mut comparison = Comparison {
+                    day,
+                    part,
+                    before,
+                    after,
+                    regression: false,
+                };
+
+                comparison.regression = match (before, comparison.change()) {
+                    (Some(before), Some(change)) => {
+                        change > threshold && after.saturating_sub(before) >= NOISE_FLOOR
+                    }
+                    _ => false,
+                };
+
+                comparison
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&str> for Baseline {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let document = Value::try_from(value)?;
+
+        let name = document
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or("missing baseline name")?
+            .to_string();
+
+        let mut timings = BTreeMap::new();
+
+        for timing in document
+            .get("timings")
+            .and_then(Value::as_array)
+            .ok_or("missing baseline timings")?
+        {
+            let field = |key: &str| timing.get(key).and_then(Value::as_u64);
+
+            let (Some(day), Some(part), Some(time)) =
+                (field("day"), field("part"), field("time_ns"))
+            else {
+                return Err("expected day, part and time_ns in every timing".to_string());
+            };
+
+            timings.insert((day as u8, part as u8), Duration::from_nanos(time));
+        }
+
+        Ok(Self { name, timings })
+    }
+}
+
+/// `dayNptM` for a part, `dayN parse` for [`PARSE_PART`].
+pub fn timing_name(day: u8, part: u8) -> String {
+    match part {
+        PARSE_PART => format!("day{} parse", day),
+        part => format!("day{}pt{}", day, part),
+    }
+}
+
+pub fn render(baseline: &Baseline, comparisons: &[Comparison]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "{:<12}{:>15}{:>15}{:>10}",
+        format!("vs {}", baseline.name),
+        "before",
+        "after",
+        "change"
+    )
+    .unwrap();
+
+    for comparison in comparisons {
+        let before = comparison
+            .before
+            .map(|before| format!("{:?}", before))
+            .unwrap_or_else(|| "-".to_string());
+
+        let change = match (comparison.before, comparison.change()) {
+            (None, _) => "new".to_string(),
+            (Some(_), None) => "-".to_string(),
+            (Some(_), Some(change)) => format!("{:+.1}%", change),
+        };
+
+        writeln!(
+            output,
+            "{:<12}{:>15}{:>15}{:>10}{}",
+            timing_name(comparison.day, comparison.part),
+            before,
+            format!("{:?}", comparison.after),
+            change,
+            if comparison.regression {
+                "  REGRESSION"
+            } else {
+                ""
+            }
+        )
+        .unwrap();
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|comparison| comparison.regression)
+        .count();
+
+    writeln!(output).unwrap();
+    writeln!(output, "{} regressions", regressions).unwrap();
+
+    output
+}
+
+#[test]
+fn baseline_round_trip_and_compare() {
+    let baseline = Baseline {
+        name: "main".to_string(),
+        timings: BTreeMap::from([
+            ((1, 1), Duration::from_millis(10)),
+            ((1, 2), Duration::from_micros(10)),
+            ((2, 1), Duration::from_millis(10)),
+        ]),
+    };
+
+    assert_eq!(
+        Baseline::try_from(baseline.to_json().as_str()).unwrap(),
+        baseline
+    );
+
+    let current = Baseline {
+        name: "current".to_string(),
+        timings: BTreeMap::from([
+            ((1, 1), Duration::from_millis(12)),
+            ((1, 2), Duration::from_micros(20)),
+            ((2, 1), Duration::from_millis(9)),
+            ((3, 1), Duration::from_millis(1)),
+        ]),
+    };
+
+    let comparisons = baseline.compare(&current, 10.0);
+    let regressions: Vec<(u8, u8)> = comparisons
+        .iter()
+        .filter(|comparison| comparison.regression)
+        .map(|comparison| (comparison.day, comparison.part))
+        .collect();
+
+    assert_eq!(regressions, vec![(1, 1)]);
+    assert_eq!(comparisons[0].change().map(f64::round), Some(20.0));
+    assert_eq!(comparisons[3].before, None);
+}
+
+#[test]
+fn counts_parse_time_once_per_day() {
+    use crate::runner::{Schedule, SolveResult};
+
+    let result = |part: u8, status: Status| {
+        let solve_time = Duration::from_millis(part as u64);
+        SolveResult::fixture(1, part, None, status, Duration::from_millis(5), solve_time)
+    };
+
+    let run = Run {
+        results: vec![result(1, Status::Pass), result(2, Status::Todo)],
+        real_time: Duration::from_millis(8),
+        schedule: Schedule::Sequential,
+        threads: 1,
+    };
+
+    assert_eq!(
+        Baseline::from_run("current", &run).timings,
+        BTreeMap::from([
+            ((1, PARSE_PART), Duration::from_millis(5)),
+            ((1, 1), Duration::from_millis(1)),
+        ])
+    );
+}
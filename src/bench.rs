@@ -0,0 +1,171 @@
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    baseline::{timing_name, PARSE_PART},
+    input::Inputs,
+    runner::{catch_panic, Job, Status},
+};
+
+pub const DEFAULT_RUNS: usize = 10;
+pub const DEFAULT_WARMUP: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl From<Vec<Duration>> for Stats {
+    fn from(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "at least one sample");
+
+        samples.sort();
+
+        let runs = samples.len();
+        let nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        let median = if runs % 2 == 0 {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        let p95_rank = ((runs as f64) * 0.95).ceil() as usize;
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95: samples[p95_rank.max(1) - 1],
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub stats: Result<Stats, Status>,
+}
+
+pub fn bench(jobs: &[Job], inputs: &Inputs, warmup: usize, runs: usize) -> Vec<BenchResult> {
+    let mut results: Vec<BenchResult> = vec![];
+
+    for job in jobs {
+        let day = job.solution.day();
+
+        // The parts share the parsed input, so parsing is benchmarked once per day.
+        if results.last().map(|result| result.day) != Some(day) {
+            results.push(BenchResult {
+                day,
+                part: PARSE_PART,
+                stats: bench_parse(job, inputs, warmup, runs),
+            });
+        }
+
+        results.push(BenchResult {
+            day,
+            part: job.part,
+            stats: bench_job(job, inputs, warmup, runs),
+        });
+    }
+
+    results
+}
+
+fn sample(warmup: usize, runs: usize, mut f: impl FnMut()) -> Stats {
+    for _ in 0..warmup {
+        f();
+    }
+
+    let samples = (0..runs.max(1)).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed()
+    });
+
+    Stats::from(samples.collect::<Vec<_>>())
+}
+
+fn bench_parse(job: &Job, inputs: &Inputs, warmup: usize, runs: usize) -> Result<Stats, Status> {
+    let input = inputs.load(job.solution.day()).map_err(Status::NoInput)?;
+
+    // Unused results could be optimized away, and part of the work with them.
+    catch_panic(|| {
+        sample(warmup, runs, || {
+            black_box(job.solution.parse(black_box(&input)));
+        })
+    })
+}
+
+fn bench_job(job: &Job, inputs: &Inputs, warmup: usize, runs: usize) -> Result<Stats, Status> {
+    let input = inputs.load(job.solution.day()).map_err(Status::NoInput)?;
+
+    catch_panic(|| {
+        let model = job.solution.parse(&input);
+
+        sample(warmup, runs, || {
+            black_box(job.solution.solve(job.part, black_box(&model)));
+        })
+    })
+}
+
+pub fn render(results: &[BenchResult]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "{:<12}{:>6}{:>14}{:>14}{:>14}{:>14}{:>14}",
+        "", "runs", "min", "median", "mean", "p95", "stddev"
+    )
+    .unwrap();
+
+    for result in results {
+        let prefix = timing_name(result.day, result.part);
+
+        match &result.stats {
+            Ok(stats) => writeln!(
+                output,
+                "{:<12}{:>6}{:>14}{:>14}{:>14}{:>14}{:>14}",
+                prefix,
+                stats.runs,
+                format!("{:?}", stats.min),
+                format!("{:?}", stats.median),
+                format!("{:?}", stats.mean),
+                format!("{:?}", stats.p95),
+                format!("{:?}", stats.stddev),
+            ),
+            Err(status @ (Status::NoInput(message) | Status::Panicked(message))) => {
+                writeln!(output, "{:<12}{} ({})", prefix, status, message)
+            }
+            Err(status) => writeln!(output, "{:<12}{}", prefix, status),
+        }
+        .unwrap();
+    }
+
+    output
+}
+
+#[test]
+fn stats_from_samples() {
+    let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+    let stats = Stats::from(samples);
+
+    assert_eq!(stats.runs, 5);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.mean, Duration::from_millis(3));
+    assert_eq!(stats.p95, Duration::from_millis(5));
+    assert_eq!(stats.stddev, Duration::from_nanos(1_414_214));
+}
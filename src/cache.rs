@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use crate::{
+    answer::Answer,
+    answers::{escape, unescape},
+    input::Inputs,
+    registry,
+    runner::{Run, Status},
+};
+
+pub const CACHE_FILE: &str = ".cache/answers.txt";
+
+/// Answers from earlier runs, keyed by day, part and input hash. An entry only counts for
+/// the solution version that produced it, see `Solution::version`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Cache {
+    pub entries: BTreeMap<(u8, u8, u64), (u32, Answer)>,
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable between Rust releases.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl Cache {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Cache::try_from(content.as_str())
+                .map_err(|err| format!("{}: {}", path.display(), err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Cache::default()),
+            Err(err) => Err(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("{}: {}", parent.display(), err))?;
+        }
+
+        fs::write(path, self.to_string()).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn clear(path: &Path) -> Result<(), String> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8, version: u32, hash: u64) -> Option<&Answer> {
+        match self.entries.get(&(day, part, hash)) {
+            Some((cached_version, answer)) if *cached_version == version => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// Adds the answers of `run` that were solved rather than taken from the cache, as long
+    /// as they didn't contradict a known answer.
+    pub fn update(&mut self, run: &Run, inputs: &Inputs) {
+        for result in &run.results {
+            if !matches!(result.status, Status::Pass | Status::Unknown) {
+                continue;
+            }
+
+            let (Some(answer), Some(solution), Ok(input)) = (
+                &result.answer,
+                registry::find(result.day),
+                inputs.load(result.day),
+            ) else {
+                continue;
+            };
+
+            self.entries.insert(
+                (result.day, result.part, hash(&input)),
+                (solution.version(), answer.clone()),
+            );
+        }
+    }
+}
+
+impl TryFrom<&str> for Cache {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut cache = Cache::default();
+
+        for (number, line) in value.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parsed = line.split_once(": ").and_then(|(key, answer)| {
+                let mut fields = key.split(' ');
+                let (day, part) = fields.next()?.strip_prefix("day")?.split_once("pt")?;
+                let version = fields.next()?.strip_prefix('v')?.parse().ok()?;
+                let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+                Some((
+                    (day.parse().ok()?, part.parse().ok()?, hash),
+                    (version, Answer::parse(&unescape(answer))),
+                ))
+            });
+
+            let Some((key, entry)) = parsed else {
+                return Err(format!(
+                    "line {}: expected 'dayNptM vVERSION HASH: answer'",
+                    number + 1
+                ));
+            };
+
+            cache.entries.insert(key, entry);
+        }
+
+        Ok(cache)
+    }
+}
+
+impl Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part, hash), (version, answer)) in &self.entries {
+            writeln!(
+                f,
+                "day{}pt{} v{} {:016x}: {}",
+                day,
+                part,
+                version,
+                hash,
+                escape(&answer.to_string())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn cache_round_trip() {
+    let mut cache = Cache::default();
+    cache
+        .entries
+        .insert((10, 2, hash("noop")), (3, Answer::bitmap("#..#\n.##.")));
+
+    let loaded = Cache::try_from(cache.to_string().as_str()).unwrap();
+
+    assert_eq!(loaded, cache);
+    assert_eq!(
+        loaded.get(10, 2, 3, hash("noop")),
+        Some(&Answer::bitmap("#..#\n.##."))
+    );
+    assert_eq!(loaded.get(10, 2, 4, hash("noop")), None);
+    assert_eq!(loaded.get(10, 2, 3, hash("addx 1")), None);
+    assert_ne!(hash("a"), hash("b"));
+    assert!(Cache::try_from("day1pt1: 5").is_err());
+}
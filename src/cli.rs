@@ -0,0 +1,530 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    baseline::DEFAULT_THRESHOLD,
+    bench::{DEFAULT_RUNS, DEFAULT_WARMUP},
+    extra::{parse_size, ColorMode, Region, Sink, Viewport},
+    input::InputSource,
+    registry,
+    report::{Format, REPORT_TITLE},
+    runner::Schedule,
+    solution::Params,
+};
+
+pub const USAGE: &str = "\
+Usage: aoc2022 [COMMAND] [DAYS] [OPTIONS]
+
+Commands:
+  run       Solve the selected days (default)
+  bench     Benchmark the selected days
+  list      List the available days
+  verify    Check the selected days against known answers
+  new       Create a new day from the template, e.g. new 20
+  watch     Re-run a day on its example and input whenever they or the source
+            change, e.g. watch 17
+
+Days:
+  A comma separated list of days and ranges, e.g. 1-5,9 (default: all)
+
+Options:
+  -d, --days <DAYS>       Same as the positional DAYS argument
+  -p, --part <PART>       Only solve part 1 or 2
+  -i, --input <FILE>      Read the input of a single day from FILE, or stdin when
+                          FILE is -
+      --input-dir <DIR>   Read inputs from DIR/dayN.txt (default: input)
+  -v, --visualize         Print visualizations to stderr while solving
+      --visualize-dir <DIR>
+                          Save visualizations as DIR/dayNptM-FRAME.txt instead
+      --color <WHEN>      Color visualizations: auto, always or never (default:
+                          auto, when printing to a terminal)
+      --crop <X,Y,WxH>    Only show the given region of graph visualizations and
+                          SVG images, e.g. 480,0,40x30
+      --scale <N>         Show every Nth cell of graph visualizations and SVG
+                          images
+      --tile <WxH>        Split printed graph visualizations into tiles of at most
+                          W by H cells
+      --svg <DIR>         Save graph visualizations as DIR/dayNptM.svg
+      --record <DIR>      Record simulations step by step as DIR/dayNptM.cast for
+                          asciinema and DIR/dayNptM.gif
+      --tui               Show the run live in an interactive table, select a
+                          row with the arrow keys to see its answer (Unix only)
+  -f, --format <FORMAT>   Output format: text, json, csv, junit, markdown or html
+                          (default: text)
+  -o, --output <FILE>     Also save the report to FILE, in the format of its
+                          extension: .txt, .json, .csv, .xml, .md or .html. Can
+                          be repeated
+      --title <TITLE>     Title of markdown and HTML reports (default: Advent of
+                          Code 2022)
+      --answers <FILE>    Known answers to check against (default: answers.txt
+                          next to the inputs)
+      --save-answers      Add answers that are not known yet to the answers file
+  -P, --param <KEY=VALUE>
+                          Configure the solution, e.g. the smaller search area of
+                          an example. Needs a single day, can be repeated
+      --cache             Reuse the answers of earlier runs with --cache for inputs
+                          that haven't changed, and cache the new ones
+      --clear-cache       Remove all cached answers before running
+      --timeout <SECS>    Give up on parsing or a part after SECS seconds
+      --schedule <MODE>   How to schedule the solutions: sequential, parallel or
+                          longest-first (default: parallel). longest-first orders
+                          the days by the timings of --baseline
+      --threads <N>       Days solved at once by the parallel schedules, and
+                          worker threads of each solution (default: one per CPU)
+      --runs <N>          Timed runs per solution when benchmarking (default: 10)
+      --warmup <N>        Untimed runs before benchmarking (default: 2)
+      --save-baseline <NAME>
+                          Save the timings as baselines/NAME.json
+      --baseline <NAME>   Compare the timings against a saved baseline
+      --threshold <PCT>   Slowdown in percent that counts as a regression
+                          (default: 10)
+      --fail-on-regression
+                          Exit with an error when a regression is found
+  -h, --help              Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Bench,
+    List,
+    Verify,
+    New,
+    Watch,
+    Help,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub command: Command,
+    pub days: Vec<u8>,
+    pub parts: Vec<u8>,
+    pub input: InputSource,
+    pub sink: Sink,
+    pub color: ColorMode,
+    pub viewport: Viewport,
+    pub svg: Option<PathBuf>,
+    pub record: Option<PathBuf>,
+    pub tui: bool,
+    pub format: Format,
+    pub outputs: Vec<(Format, PathBuf)>,
+    pub title: String,
+    pub answers: Option<PathBuf>,
+    pub save_answers: bool,
+    pub params: Params,
+    pub cache: bool,
+    pub clear_cache: bool,
+    pub timeout: Option<Duration>,
+    pub schedule: Schedule,
+    pub threads: Option<usize>,
+    pub runs: usize,
+    pub warmup: usize,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub threshold: f64,
+    pub fail_on_regression: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            command: Command::Run,
+            days: registry::SOLUTIONS
+                .iter()
+                .map(|solution| solution.day())
+                .collect(),
+            parts: vec![1, 2],
+            input: InputSource::default(),
+            sink: Sink::Disabled,
+            color: ColorMode::default(),
+            viewport: Viewport::default(),
+            svg: None,
+            record: None,
+            tui: false,
+            format: Format::Text,
+            outputs: vec![],
+            title: REPORT_TITLE.to_string(),
+            answers: None,
+            save_answers: false,
+            params: Params::new(),
+            cache: false,
+            clear_cache: false,
+            timeout: None,
+            schedule: Schedule::default(),
+            threads: None,
+            runs: DEFAULT_RUNS,
+            warmup: DEFAULT_WARMUP,
+            baseline: None,
+            save_baseline: None,
+            threshold: DEFAULT_THRESHOLD,
+            fail_on_regression: false,
+        }
+    }
+}
+
+pub fn parse_args<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut args = args.into_iter().peekable();
+    let mut days: Option<Vec<u8>> = None;
+
+    if let Some(command) = args.peek() {
+        let command = match command.as_str() {
+            "run" => Some(Command::Run),
+            "bench" => Some(Command::Bench),
+            "list" => Some(Command::List),
+            "verify" => Some(Command::Verify),
+            "new" => Some(Command::New),
+            "watch" => Some(Command::Watch),
+            "help" => Some(Command::Help),
+            _ => None,
+        };
+
+        if let Some(command) = command {
+            options.command = command;
+            args.next();
+        }
+    }
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline_value {
+                Some(value) => Ok(value.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("missing value for {}", name)),
+            }
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => options.command = Command::Help,
+            "-d" | "--days" => days = Some(parse_days(&value(&flag)?)?),
+            "-p" | "--part" => options.parts = vec![parse_part(&value(&flag)?)?],
+            "-i" | "--input" => {
+                let path = value(&flag)?;
+                options.input = if path == "-" {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(path))
+                };
+            }
+            "--input-dir" => options.input = InputSource::Directory(PathBuf::from(value(&flag)?)),
+            "-v" | "--visualize" => options.sink = Sink::Stderr,
+            "--visualize-dir" => options.sink = Sink::Files(PathBuf::from(value(&flag)?)),
+            "--color" => options.color = ColorMode::try_from(value(&flag)?.as_str())?,
+            "--crop" => options.viewport.crop = Some(Region::try_from(value(&flag)?.as_str())?),
+            "--scale" => {
+                options.viewport.scale = match parse_count(&flag, &value(&flag)?)? {
+                    0 => return Err("--scale needs to be at least 1".to_string()),
+                    scale => scale as u32,
+                }
+            }
+            "--tile" => {
+                let tile = value(&flag)?;
+                options.viewport.tile = Some(parse_size(&tile).ok_or_else(|| {
+                    format!("invalid value '{}' for --tile, expected WIDTHxHEIGHT", tile)
+                })?);
+            }
+            "--svg" => options.svg = Some(PathBuf::from(value(&flag)?)),
+            "--record" => options.record = Some(PathBuf::from(value(&flag)?)),
+            "-f" | "--format" => options.format = Format::try_from(value(&flag)?.as_str())?,
+            "-o" | "--output" => {
+                let path = PathBuf::from(value(&flag)?);
+                options.outputs.push((Format::for_path(&path)?, path));
+            }
+            "--title" => options.title = value(&flag)?,
+            "--answers" => options.answers = Some(PathBuf::from(value(&flag)?)),
+            "--save-answers" => options.save_answers = true,
+            "-P" | "--param" => {
+                let param = value(&flag)?;
+                let Some((key, value)) = param.split_once('=') else {
+                    return Err(format!("invalid parameter '{}', expected KEY=VALUE", param));
+                };
+                options
+                    .params
+                    .insert(key.trim().to_string(), value.trim().to_string());
+            }
+            "--tui" => options.tui = true,
+            "--cache" => options.cache = true,
+            "--clear-cache" => options.clear_cache = true,
+            "--timeout" => options.timeout = Some(parse_seconds(&flag, &value(&flag)?)?),
+            "--schedule" => options.schedule = Schedule::try_from(value(&flag)?.as_str())?,
+            "--threads" => {
+                options.threads = match parse_count(&flag, &value(&flag)?)? {
+                    0 => return Err("--threads needs at least one thread".to_string()),
+                    threads => Some(threads),
+                }
+            }
+            "--runs" => options.runs = parse_count(&flag, &value(&flag)?)?.max(1),
+            "--warmup" => options.warmup = parse_count(&flag, &value(&flag)?)?,
+            "--baseline" => options.baseline = Some(value(&flag)?),
+            "--save-baseline" => options.save_baseline = Some(value(&flag)?),
+            "--threshold" => options.threshold = parse_percent(&flag, &value(&flag)?)?,
+            "--fail-on-regression" => options.fail_on_regression = true,
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ if days.is_none() => days = Some(parse_days(&flag)?),
+            _ => return Err(format!("unexpected argument '{}'", flag)),
+        }
+    }
+
+    if options.schedule == Schedule::LongestFirst && options.baseline.is_none() {
+        return Err("longest-first needs past timings, pass --baseline NAME".to_string());
+    }
+
+    if options.command == Command::New {
+        return match days.as_deref() {
+            Some([day]) if registry::find(*day).is_some() => {
+                Err(format!("day {} already has a solution", day))
+            }
+            Some([day]) => {
+                options.days = vec![*day];
+                Ok(options)
+            }
+            _ => Err("new expects a single day".to_string()),
+        };
+    }
+
+    if let Some(days) = days {
+        for day in &days {
+            if registry::find(*day).is_none() {
+                return Err(format!("day {} has no solution", day));
+            }
+        }
+
+        options.days = days;
+    }
+
+    if options.command == Command::Watch {
+        if options.days.len() != 1 {
+            return Err("watch expects a single day".to_string());
+        }
+
+        if matches!(options.input, InputSource::Stdin) {
+            return Err("watch can't read the input from stdin".to_string());
+        }
+    }
+
+    if options.tui {
+        // The terminal is put into raw mode with stty through /dev/tty.
+        if !cfg!(unix) {
+            return Err("--tui needs a Unix terminal".to_string());
+        }
+
+        if !matches!(options.command, Command::Run | Command::Verify) {
+            return Err("--tui only works with run and verify".to_string());
+        }
+
+        if options.format != Format::Text {
+            return Err(
+                "--tui replaces the report, it can't be combined with --format".to_string(),
+            );
+        }
+
+        if options.sink != Sink::Disabled {
+            return Err(
+                "--tui shows the visualizations itself, it can't be combined with -v or \
+                 --visualize-dir"
+                    .to_string(),
+            );
+        }
+    }
+
+    if !options.params.is_empty() && options.days.len() != 1 {
+        return Err("--param needs a single day".to_string());
+    }
+
+    // Every selected day would read the same input.
+    if matches!(options.input, InputSource::File(_) | InputSource::Stdin) && options.days.len() != 1
+    {
+        return Err("--input needs a single day".to_string());
+    }
+
+    Ok(options)
+}
+
+pub fn parse_days(spec: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+
+    for item in spec.split(',').map(str::trim) {
+        let (from, to) = match item.split_once('-') {
+            Some((from, to)) => (parse_day(from)?, parse_day(to)?),
+            None => (parse_day(item)?, parse_day(item)?),
+        };
+
+        if from > to {
+            return Err(format!("invalid day range '{}'", item));
+        }
+
+        for day in from..=to {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    days.sort();
+
+    Ok(days)
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    match value.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day '{}', expected a number from 1 to 25",
+            value
+        )),
+    }
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value.trim() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part '{}', expected 1 or 2", value)),
+    }
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<usize, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value '{}' for {}, expected a number", value, flag))
+}
+
+fn parse_seconds(flag: &str, value: &str) -> Result<Duration, String> {
+    match value.trim().parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!(
+            "invalid value '{}' for {}, expected a number of seconds",
+            value, flag
+        )),
+    }
+}
+
+fn parse_percent(flag: &str, value: &str) -> Result<f64, String> {
+    match value.trim().trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent.is_finite() && percent >= 0.0 => Ok(percent),
+        _ => Err(format!(
+            "invalid value '{}' for {}, expected a percentage",
+            value, flag
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{parse_args, parse_days, ColorMode, Command, Format, Schedule, Sink};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn day_spec() {
+        assert_eq!(parse_days("1-5,9").unwrap(), vec![1, 2, 3, 4, 5, 9]);
+        assert_eq!(parse_days("9,3,3-4").unwrap(), vec![3, 4, 9]);
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("one").is_err());
+    }
+
+    #[test]
+    fn command_line() {
+        let options = parse_args(args("run 1-3 --part 2 -v")).unwrap();
+        assert_eq!(options.command, Command::Run);
+        assert_eq!(options.days, vec![1, 2, 3]);
+        assert_eq!(options.parts, vec![2]);
+        assert_eq!(options.sink, Sink::Stderr);
+        assert!(!options.cache);
+        assert!(parse_args(args("run --cache")).unwrap().cache);
+
+        let options = parse_args(args("run 14 --visualize-dir frames")).unwrap();
+        assert_eq!(options.sink, Sink::Files(PathBuf::from("frames")));
+
+        let options = parse_args(args("run 14 --svg images --color never --record rec")).unwrap();
+        assert_eq!(options.svg, Some(PathBuf::from("images")));
+        assert_eq!(options.record, Some(PathBuf::from("rec")));
+        assert_eq!(options.color, ColorMode::Never);
+        assert!(parse_args(args("run --color rainbow")).is_err());
+
+        let options =
+            parse_args(args("run 14 --crop 480,-2,40x30 --scale 2 --tile 60x20")).unwrap();
+        assert_eq!(
+            options.viewport.crop.map(|crop| (crop.x, crop.y)),
+            Some((480, -2))
+        );
+        assert_eq!(options.viewport.scale, 2);
+        assert_eq!(options.viewport.tile, Some((60, 20)));
+        assert!(parse_args(args("run --tile 60")).is_err());
+        assert!(parse_args(args("run --crop 1,2,0x3")).is_err());
+        assert!(parse_args(args("run --scale 0")).is_err());
+
+        let options = parse_args(args("--days=4 --format json")).unwrap();
+        assert_eq!(options.command, Command::Run);
+        assert_eq!(options.days, vec![4]);
+        assert_eq!(options.format, Format::Json);
+
+        let options = parse_args(args("run -o release.md --output=release.html")).unwrap();
+        assert_eq!(
+            options.outputs,
+            vec![
+                (Format::Markdown, PathBuf::from("release.md")),
+                (Format::Html, PathBuf::from("release.html"))
+            ]
+        );
+
+        assert!(parse_args(args("run --part 3")).is_err());
+        assert!(parse_args(args("run --bogus")).is_err());
+        assert!(parse_args(args("run --input")).is_err());
+        assert!(parse_args(args("run 1-6 -i input/day1.txt")).is_err());
+        assert!(parse_args(args("run 1,2 -i -")).is_err());
+        assert!(parse_args(args("run 1 -i -")).is_ok());
+        assert!(parse_args(args("run 25")).is_err());
+        assert!(parse_args(args("run -f yaml")).is_err());
+        assert!(parse_args(args("run -o report.pdf")).is_err());
+        assert!(parse_args(args("bench --runs many")).is_err());
+        assert!(parse_args(args("run --timeout 0")).is_err());
+        assert!(parse_args(args("new 1")).is_err());
+        assert!(parse_args(args("new 24-25")).is_err());
+        assert_eq!(parse_args(args("new 25")).unwrap().days, vec![25]);
+
+        let options = parse_args(args("run --timeout 1.5")).unwrap();
+        assert_eq!(options.timeout, Some(Duration::from_millis(1500)));
+
+        let options = parse_args(args("run --schedule sequential --threads 2")).unwrap();
+        assert_eq!(options.schedule, Schedule::Sequential);
+        assert_eq!(options.threads, Some(2));
+
+        assert!(parse_args(args("run --threads 0")).is_err());
+        assert!(parse_args(args("run --schedule random")).is_err());
+        assert!(parse_args(args("run --schedule longest-first")).is_err());
+        assert!(parse_args(args("run --schedule longest-first --baseline main")).is_ok());
+
+        let options = parse_args(args("run 15 -P row=10 --param search_size=20")).unwrap();
+        assert_eq!(options.params.get("row").map(String::as_str), Some("10"));
+        assert_eq!(options.params.len(), 2);
+
+        assert!(parse_args(args("run -P row=10")).is_err());
+        assert!(parse_args(args("run 15 -P row")).is_err());
+
+        assert_eq!(
+            parse_args(args("watch 17")).unwrap().command,
+            Command::Watch
+        );
+        assert!(parse_args(args("watch")).is_err());
+        assert!(parse_args(args("watch 17 --input -")).is_err());
+
+        assert_eq!(parse_args(args("verify 1-5 --tui")).is_ok(), cfg!(unix));
+        assert!(parse_args(args("bench --tui")).is_err());
+        assert!(parse_args(args("run --tui --format json")).is_err());
+        assert!(parse_args(args("run --tui --visualize-dir frames")).is_err());
+    }
+}
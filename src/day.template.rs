@@ -1,6 +1,34 @@
-pub fn solve_1(input: &str) -> String {
+use crate::{answer::Answer, solution::Solution};
+
+pub fn solve_1(_input: &str) -> Answer {
     todo!()
 }
-pub fn solve_2(input: &str) -> String {
+pub fn solve_2(_input: &str) -> Answer {
     todo!()
 }
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Model = String;
+
+    fn name(&self) -> &'static str {
+        "DayN"
+    }
+
+    fn day(&self) -> u8 {
+        N
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.to_string()
+    }
+
+    fn part1(&self, input: &Self::Model) -> Answer {
+        solve_1(input)
+    }
+
+    fn part2(&self, input: &Self::Model) -> Answer {
+        solve_2(input)
+    }
+}
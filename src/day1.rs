@@ -1,26 +1,59 @@
-pub fn parse_inventories(input: &str) -> impl Iterator<Item = Vec<u64>> + '_ {
-    input.split("\n\n").map(|inventory_string| {
-        inventory_string
-            .lines()
-            .map(|line| line.parse::<u64>().expect("parse inventory line as u64"))
-            .collect::<Vec<u64>>()
-    })
+use crate::{answer::Answer, solution::Solution};
+
+pub fn parse_inventories(input: &str) -> Vec<Vec<u64>> {
+    input
+        .split("\n\n")
+        .map(|inventory_string| {
+            inventory_string
+                .lines()
+                .map(|line| line.parse::<u64>().expect("parse inventory line as u64"))
+                .collect::<Vec<u64>>()
+        })
+        .collect()
 }
 
-pub fn solve_1(input: &str) -> String {
-    parse_inventories(input)
+pub fn solve_1(inventories: &[Vec<u64>]) -> Answer {
+    inventories
+        .iter()
         .map(|i| i.iter().sum::<u64>())
         .max()
         .expect("find maximum")
-        .to_string()
+        .into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut inventory_sums: Vec<u64> = parse_inventories(input)
-        .map(|inventory| inventory.into_iter().sum::<u64>())
+pub fn solve_2(inventories: &[Vec<u64>]) -> Answer {
+    let mut inventory_sums: Vec<u64> = inventories
+        .iter()
+        .map(|inventory| inventory.iter().sum::<u64>())
         .collect();
 
     inventory_sums.sort_by(|a, b| b.cmp(a));
 
-    inventory_sums.into_iter().take(3).sum::<u64>().to_string()
+    inventory_sums.into_iter().take(3).sum::<u64>().into()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Model = Vec<Vec<u64>>;
+
+    fn name(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_inventories(input)
+    }
+
+    fn part1(&self, inventories: &Self::Model) -> Answer {
+        solve_1(inventories)
+    }
+
+    fn part2(&self, inventories: &Self::Model) -> Answer {
+        solve_2(inventories)
+    }
 }
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::extra::visualize;
+use crate::{answer::Answer, extra::visualize, solution::Solution};
 
 #[derive(Debug, Clone)]
 pub enum Instruction {
@@ -74,11 +74,11 @@ impl From<&str> for Instruction {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
+pub fn solve_1(program: &[Instruction]) -> Answer {
     let mut cpu = Cpu {
         cycle: 1,
         register_x: 1,
-        program: input.lines().map(Instruction::from).collect(),
+        program: program.to_vec(),
         ..Default::default()
     };
 
@@ -91,7 +91,7 @@ pub fn solve_1(input: &str) -> String {
         }
     }
 
-    signal_strengths.iter().sum::<i64>().to_string()
+    signal_strengths.iter().sum::<i64>().into()
 }
 
 pub struct Crt {
@@ -125,11 +125,11 @@ impl Display for Crt {
     }
 }
 
-pub fn solve_2(input: &str) -> String {
+pub fn solve_2(program: &[Instruction]) -> Answer {
     let cpu = Cpu {
         cycle: 0,
         register_x: 1,
-        program: input.lines().map(Instruction::from).collect(),
+        program: program.to_vec(),
         ..Default::default()
     };
 
@@ -137,5 +137,31 @@ pub fn solve_2(input: &str) -> String {
 
     visualize(10, 2, &crt);
 
-    format!("{}", crt)
+    Answer::bitmap(&crt.to_string())
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Model = Vec<Instruction>;
+
+    fn name(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.lines().map(Instruction::from).collect()
+    }
+
+    fn part1(&self, program: &Self::Model) -> Answer {
+        solve_1(program)
+    }
+
+    fn part2(&self, program: &Self::Model) -> Answer {
+        solve_2(program)
+    }
 }
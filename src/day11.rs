@@ -1,8 +1,12 @@
+use crate::{answer::Answer, solution::Solution};
+
+#[derive(Clone)]
 pub enum OperationValue {
     Old,
     Value(usize),
 }
 
+#[derive(Clone)]
 pub enum Operation {
     Add(OperationValue, OperationValue),
     Multiply(OperationValue, OperationValue),
@@ -13,6 +17,7 @@ pub struct Throw {
     pub monkey_index: usize,
 }
 
+#[derive(Clone)]
 pub struct Monkey {
     pub items: Vec<usize>,
     pub operation: Operation,
@@ -158,8 +163,8 @@ pub fn parse_monkeys(input: &str) -> Vec<Monkey> {
         .collect()
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut monkeys = parse_monkeys(input);
+pub fn solve_1(monkeys: &[Monkey]) -> Answer {
+    let mut monkeys = monkeys.to_vec();
 
     for _round in 0..20 {
         for monkey_index in 0..monkeys.len() {
@@ -181,11 +186,11 @@ pub fn solve_1(input: &str) -> String {
     inspections.sort();
     inspections.reverse();
 
-    inspections.iter().take(2).product::<usize>().to_string()
+    inspections.iter().take(2).product::<usize>().into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut monkeys = parse_monkeys(input);
+pub fn solve_2(monkeys: &[Monkey]) -> Answer {
+    let mut monkeys = monkeys.to_vec();
 
     let monkey_cycle: usize = monkeys.iter().map(|monkey| monkey.test).product();
 
@@ -209,5 +214,31 @@ pub fn solve_2(input: &str) -> String {
     inspections.sort();
     inspections.reverse();
 
-    inspections.iter().take(2).product::<usize>().to_string()
+    inspections.iter().take(2).product::<usize>().into()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Model = Vec<Monkey>;
+
+    fn name(&self) -> &'static str {
+        "Monkey in the Middle"
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_monkeys(input)
+    }
+
+    fn part1(&self, monkeys: &Self::Model) -> Answer {
+        solve_1(monkeys)
+    }
+
+    fn part2(&self, monkeys: &Self::Model) -> Answer {
+        solve_2(monkeys)
+    }
 }
@@ -6,7 +6,11 @@ use std::{
 
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
-use crate::extra::visualize;
+use crate::{
+    answer::Answer,
+    extra::{visualize_colored, Palette},
+    solution::Solution,
+};
 
 #[derive(Debug, Clone)]
 pub enum Move {
@@ -296,6 +300,18 @@ impl Climber {
     }
 }
 
+/// The path in green between a blue start and a red end.
+pub const PALETTE: Palette = Palette(&[
+    ('▲', "32"),
+    ('▼', "32"),
+    ('◀', "32"),
+    ('▶', "32"),
+    ('S', "1;34"),
+    ('E', "1;31"),
+    ('X', "1;35"),
+    ('·', "2"),
+]);
+
 impl Display for Climber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let visited: Vec<Point> = self.visited.clone().into_iter().collect();
@@ -356,8 +372,8 @@ pub fn manage_climbers(climbers: Vec<Climber>, climb: &mut Climb) -> Vec<Climber
     next
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut climb = Climb::from(input);
+pub fn solve_1(climb: &Climb) -> Answer {
+    let mut climb = climb.clone();
     let mut climbers = vec![climb.starting_climber.clone()];
 
     let mut best_climbers: Vec<Climber>;
@@ -374,9 +390,9 @@ pub fn solve_1(input: &str) -> String {
 
     let best_climber = climbers.first().unwrap();
 
-    visualize(12, 1, &best_climber);
+    visualize_colored(12, 1, &best_climber, &PALETTE);
 
-    best_climber.moves.to_string()
+    best_climber.moves.into()
 }
 
 pub fn best_climber_from_point(
@@ -412,10 +428,9 @@ pub fn best_climber_from_point(
     climbers.first().cloned()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let climb = Climb::from(input);
+pub fn solve_2(climb: &Climb) -> Answer {
     let starting_points = climb.starting_points();
-    let climb = Arc::new(RwLock::new(climb));
+    let climb = Arc::new(RwLock::new(climb.clone()));
 
     let best = usize::MAX;
 
@@ -438,7 +453,33 @@ pub fn solve_2(input: &str) -> String {
 
     let best = best_climber.unwrap();
 
-    visualize(12, 2, &best);
+    visualize_colored(12, 2, &best, &PALETTE);
+
+    best.moves.into()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Model = Climb;
+
+    fn name(&self) -> &'static str {
+        "Hill Climbing Algorithm"
+    }
 
-    best.moves.to_string()
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        Climb::from(input)
+    }
+
+    fn part1(&self, climb: &Self::Model) -> Answer {
+        solve_1(climb)
+    }
+
+    fn part2(&self, climb: &Self::Model) -> Answer {
+        solve_2(climb)
+    }
 }
@@ -1,3 +1,5 @@
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum PacketData {
     Item(usize),
@@ -120,30 +122,33 @@ impl From<&str> for Packet {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    let blocks = input.split("\n\n");
+pub fn parse_pairs(input: &str) -> Vec<(Packet, Packet)> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            let line_vector: Vec<&str> = block.lines().collect();
+            assert!(line_vector.len() == 2);
+            (Packet::from(line_vector[0]), Packet::from(line_vector[1]))
+        })
+        .collect()
+}
 
+pub fn solve_1(pairs: &[(Packet, Packet)]) -> Answer {
     let mut sum = 0;
 
-    for (index, block) in blocks.enumerate() {
-        let line_vector: Vec<&str> = block.lines().collect();
-        assert!(line_vector.len() == 2);
-        let left = Packet::from(line_vector[0]);
-        let right = Packet::from(line_vector[1]);
-
+    for (index, (left, right)) in pairs.iter().enumerate() {
         if left < right {
             sum += index + 1;
         }
     }
 
-    sum.to_string()
+    sum.into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut packets: Vec<Packet> = input
-        .replace("\n\n", "\n")
-        .lines()
-        .map(Packet::from)
+pub fn solve_2(pairs: &[(Packet, Packet)]) -> Answer {
+    let mut packets: Vec<Packet> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
         .collect();
 
     let divider_one = Packet {
@@ -171,5 +176,31 @@ pub fn solve_2(input: &str) -> String {
         })
         .product();
 
-    decoder_key.to_string()
+    decoder_key.into()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Model = Vec<(Packet, Packet)>;
+
+    fn name(&self) -> &'static str {
+        "Distress Signal"
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Model) -> Answer {
+        solve_1(pairs)
+    }
+
+    fn part2(&self, pairs: &Self::Model) -> Answer {
+        solve_2(pairs)
+    }
 }
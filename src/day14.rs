@@ -4,7 +4,14 @@ use std::{
     iter::repeat,
 };
 
-use crate::extra::{visualize, ChristmasGraph, GraphMetadata};
+use crate::{
+    answer::Answer,
+    extra::{
+        visualize_graph, visualize_svg, Cells, ChristmasGraph, GraphMetadata, Palette, SvgStyle,
+    },
+    record::Recorder,
+    solution::Solution,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Cell {
@@ -91,6 +98,20 @@ impl ScanPath {
     }
 }
 
+impl From<&str> for Scan {
+    fn from(input: &str) -> Self {
+        let mut scan = Scan {
+            rocks: HashSet::new(),
+        };
+
+        for path in input.lines().map(ScanPath::from) {
+            scan.add_path(path);
+        }
+
+        scan
+    }
+}
+
 impl From<&Scan> for Simulation {
     fn from(scan: &Scan) -> Self {
         let mut max_x = i64::MIN;
@@ -183,7 +204,25 @@ impl Display for Simulation {
     }
 }
 
+/// Gray rock, settled sand and paler sand that is still flowing.
+pub const PALETTE: Palette = Palette(&[('#', "90"), ('o', "33"), ('~', "93"), ('+', "1;31")]);
+
 impl Simulation {
+    pub fn svg_style() -> SvgStyle {
+        SvgStyle::default()
+            .color('#', "#6e6259")
+            .color('o', "#e2b659")
+            .color('~', "#f3dfae")
+            .color('+', "#d9453d")
+    }
+
+    pub fn recording_style() -> SvgStyle {
+        SvgStyle {
+            cell_size: 3,
+            ..Simulation::svg_style()
+        }
+    }
+
     pub fn next_position(&self, position: (i64, i64)) -> (i64, i64) {
         let (mut x, mut y) = position;
 
@@ -285,11 +324,13 @@ impl Simulation {
         }
     }
 
-    pub fn run(&mut self) {
+    /// Drops sand until it flows off, with a frame per grain that comes to rest.
+    pub fn run(&mut self, recorder: &mut Recorder) {
         loop {
             if !self.step_resting() {
                 break;
             }
+            recorder.push(&Cells(self));
         }
         let mut step = self.next_position(self.spawn_source);
 
@@ -305,40 +346,31 @@ impl Simulation {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut scan = Scan {
-        rocks: HashSet::new(),
-    };
-
-    for path in input.lines().map(ScanPath::from) {
-        scan.add_path(path);
-    }
-
-    let mut simulation = Simulation::from(&scan);
+pub fn solve_1(scan: &Scan) -> Answer {
+    let mut simulation = Simulation::from(scan);
 
     simulation.height += 2;
     simulation.width += 2;
     simulation.x -= 1;
 
-    visualize(14, 1, &simulation);
+    visualize_graph(14, 1, &simulation, &PALETTE);
 
-    simulation.run();
+    let mut recorder = Recorder::start(14, 1);
+    simulation.run(&mut recorder);
+    recorder.finish(
+        &Cells(&simulation),
+        &PALETTE,
+        &Simulation::recording_style(),
+    );
 
-    visualize(14, 1, &simulation);
+    visualize_graph(14, 1, &simulation, &PALETTE);
+    visualize_svg(14, 1, &simulation, &Simulation::svg_style());
 
-    simulation.resting.to_string()
+    simulation.resting.into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut scan = Scan {
-        rocks: HashSet::new(),
-    };
-
-    for path in input.lines().map(ScanPath::from) {
-        scan.add_path(path);
-    }
-
-    let mut simulation = Simulation::from(&scan);
+pub fn solve_2(scan: &Scan) -> Answer {
+    let mut simulation = Simulation::from(scan);
 
     let needed_width = (simulation.height + 3) * 2;
 
@@ -350,11 +382,44 @@ pub fn solve_2(input: &str) -> String {
     simulation.width -= simulation.width % 2 + 1;
     simulation.x = simulation.spawn_source.0 - simulation.width as i64 / 2;
 
-    visualize(14, 2, &simulation);
+    visualize_graph(14, 2, &simulation, &PALETTE);
+
+    let mut recorder = Recorder::start(14, 2);
+    simulation.run(&mut recorder);
+    recorder.finish(
+        &Cells(&simulation),
+        &PALETTE,
+        &Simulation::recording_style(),
+    );
+
+    visualize_graph(14, 2, &simulation, &PALETTE);
+    visualize_svg(14, 2, &simulation, &Simulation::svg_style());
+
+    simulation.resting.into()
+}
+
+pub struct Day14;
 
-    simulation.run();
+impl Solution for Day14 {
+    type Model = Scan;
 
-    visualize(14, 2, &simulation);
+    fn name(&self) -> &'static str {
+        "Regolith Reservoir"
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        Scan::from(input)
+    }
+
+    fn part1(&self, scan: &Self::Model) -> Answer {
+        solve_1(scan)
+    }
 
-    simulation.resting.to_string()
+    fn part2(&self, scan: &Self::Model) -> Answer {
+        solve_2(scan)
+    }
 }
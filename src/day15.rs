@@ -5,6 +5,14 @@ use std::{
 
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
+use crate::{
+    answer::Answer,
+    solution::{Params, Solution},
+};
+
+const ROW: isize = 2000000;
+const SEARCH_SIZE: isize = 4000000;
+
 pub struct Sensor {
     pub x: isize,
     pub y: isize,
@@ -13,7 +21,7 @@ pub struct Sensor {
 
 impl Sensor {
     pub fn x_range(&self, y: isize) -> Option<(isize, isize)> {
-        let diff_y = (self.y - y).unsigned_abs() as usize;
+        let diff_y = (self.y - y).unsigned_abs();
 
         if diff_y >= self.reach {
             return None;
@@ -35,6 +43,10 @@ pub struct ExclusionZone {
     pub height: usize,
     pub beacons: HashSet<(isize, isize)>,
     pub sensors: Vec<Sensor>,
+    /// Row to count excluded positions on in part 1.
+    pub row: isize,
+    /// Largest coordinate the distress beacon can be at in part 2.
+    pub search_size: isize,
 }
 
 impl ExclusionZone {
@@ -50,9 +62,7 @@ impl ExclusionZone {
                 .sensors
                 .iter()
                 .filter_map(|sensor| {
-                    let Some(mut reachable_range) = sensor.x_range(at_y) else {
-                    return None
-                };
+                    let mut reachable_range = sensor.x_range(at_y)?;
 
                     reachable_range.0 = reachable_range.0.clamp(from_x, to_x);
                     reachable_range.1 = reachable_range.1.clamp(from_x, to_x);
@@ -66,7 +76,7 @@ impl ExclusionZone {
                 let (x1, x2) = ranges[0];
                 let (x3, x4) = ranges[1];
 
-                let mut values = vec![x1, x2, x3, x4];
+                let mut values = [x1, x2, x3, x4];
                 values.sort();
 
                 Some(((values[1] + 1), at_y))
@@ -89,47 +99,29 @@ impl ExclusionZone {
 
         let mut range = 0..self.sensors.len() * 2;
 
-        let Some(positive_line) = range.find_map(|i| {
-            let positive_line = ((i + 1)..self.sensors.len() * 2).find_map(|j| {
+        let positive_line = range.find_map(|i| {
+            ((i + 1)..self.sensors.len() * 2).find_map(|j| {
                 let a = positive_lines[i];
                 let b = positive_lines[j];
 
                 if (a - b).abs() == 2 {
-                    return Some(a.min(b) + 1)
+                    return Some(a.min(b) + 1);
                 }
                 None
-            });
-
-            let Some(positive) = positive_line else {
-                return None;
-            };
-
-            Some(positive)
+            })
+        })?;
 
-        }) else {
-            return None;
-        };
-
-        let Some(negative_line) = range.find_map(|i| {
-            let negative_line = ((i + 1)..self.sensors.len() * 2).find_map(|j| {
+        let negative_line = range.find_map(|i| {
+            ((i + 1)..self.sensors.len() * 2).find_map(|j| {
                 let a = negative_lines[i];
                 let b = negative_lines[j];
 
                 if (a - b).abs() == 2 {
-                    return Some(a.min(b) + 1)
+                    return Some(a.min(b) + 1);
                 }
                 None
-            });
-
-            let Some(negative) = negative_line else {
-                return None;
-            };
-
-            Some(negative)
-
-        }) else {
-            return None;
-        };
+            })
+        })?;
 
         Some((
             (positive_line + negative_line) / 2,
@@ -176,7 +168,11 @@ impl ExclusionZone {
 
 impl From<&str> for ExclusionZone {
     fn from(value: &str) -> Self {
-        let mut output = Self::default();
+        let mut output = Self {
+            row: ROW,
+            search_size: SEARCH_SIZE,
+            ..Default::default()
+        };
         let mut max_x = 0;
         let mut max_y = 0;
 
@@ -243,34 +239,18 @@ fn merge_ranges(ranges: Vec<(isize, isize)>) -> Vec<(isize, isize)> {
     merged_ranges
 }
 
-pub fn solve_1(input: &str) -> String {
-    let zone = ExclusionZone::from(input);
-
-    if cfg!(test) {
-        zone.exclusion_count(10).to_string()
-    } else {
-        zone.exclusion_count(2000000).to_string()
-    }
+pub fn solve_1(zone: &ExclusionZone) -> Answer {
+    zone.exclusion_count(zone.row).into()
 }
-pub fn solve_2(input: &str) -> String {
-    let zone = ExclusionZone::from(input);
-
-    if cfg!(test) {
-        let beacon = zone.find_beacon(0, 0, 20, 20).expect("find beacon");
-
-        let tuning_frequency = beacon.0 * 4000000 + beacon.1;
-
-        tuning_frequency.to_string()
-    } else {
-        let beacon = zone
-            .find_beacon(0, 0, 4000000, 4000000)
-            .expect("find beacon");
-        //let beacon = zone.math_find_beacon().expect("find beacon");
+pub fn solve_2(zone: &ExclusionZone) -> Answer {
+    let beacon = zone
+        .find_beacon(0, 0, zone.search_size, zone.search_size)
+        .expect("find beacon");
+    //let beacon = zone.math_find_beacon().expect("find beacon");
 
-        let tuning_frequency = beacon.0 * 4000000 + beacon.1;
+    let tuning_frequency = beacon.0 * 4000000 + beacon.1;
 
-        tuning_frequency.to_string()
-    }
+    tuning_frequency.into()
 }
 
 #[test]
@@ -280,3 +260,45 @@ fn merge_range_test() {
 
     assert_eq!(merged_ranges, [(1, 6), (8, 10), (15, 20)])
 }
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Model = ExclusionZone;
+
+    fn name(&self) -> &'static str {
+        "Beacon Exclusion Zone"
+    }
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        ExclusionZone::from(input)
+    }
+
+    fn part1(&self, zone: &Self::Model) -> Answer {
+        solve_1(zone)
+    }
+
+    fn part2(&self, zone: &Self::Model) -> Answer {
+        solve_2(zone)
+    }
+
+    fn configure(&self, zone: &mut Self::Model, params: &Params) -> Result<(), String> {
+        for (key, value) in params {
+            let value = value
+                .parse()
+                .map_err(|_| format!("invalid value '{}' for {}", value, key))?;
+
+            match key.as_str() {
+                "row" => zone.row = value,
+                "search_size" => zone.search_size = value,
+                _ => return Err(format!("unknown parameter '{}'", key)),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use std::{cmp::min, collections::HashMap, hash::Hash};
 
-type SolutionCache = HashMap<SolverInput, usize>;
+use crate::{answer::Answer, solution::Solution};
+
+pub type SolutionCache = HashMap<SolverInput, usize>;
 
 #[derive(Clone)]
 pub struct Valve {
@@ -225,16 +227,17 @@ impl From<&str> for ValveSystem {
                     .clone()
                     .into_iter()
                     .enumerate()
-                    .find(|(_, name)| neighbour.eq(name)) else {
-                        continue;
-                    };
+                    .find(|(_, name)| neighbour.eq(name))
+                else {
+                    continue;
+                };
 
                 distances[index][valve_id] = Some(1usize);
             }
         }
 
-        for identity in 0..distances.len() {
-            distances[identity][identity] = Some(0);
+        for (identity, row) in distances.iter_mut().enumerate() {
+            row[identity] = Some(0);
         }
 
         let distances = floyd_warshall(&distances);
@@ -253,9 +256,9 @@ impl From<&str> for ValveSystem {
     }
 }
 
-fn floyd_warshall(graph: &Vec<Vec<Option<usize>>>) -> Vec<Vec<Option<usize>>> {
+fn floyd_warshall(graph: &[Vec<Option<usize>>]) -> Vec<Vec<Option<usize>>> {
     let n = graph.len();
-    let mut distance = graph.clone();
+    let mut distance = graph.to_vec();
 
     for k in 0..n {
         for i in 0..n {
@@ -274,19 +277,46 @@ fn floyd_warshall(graph: &Vec<Vec<Option<usize>>>) -> Vec<Vec<Option<usize>>> {
     distance
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut solver = ValveSystemSolver {
-        cache: HashMap::new(),
-        system: ValveSystem::from(input),
-    };
+impl From<ValveSystem> for ValveSystemSolver {
+    fn from(system: ValveSystem) -> Self {
+        Self {
+            system,
+            cache: HashMap::new(),
+        }
+    }
+}
 
-    solver.solve().to_string()
+pub fn solve_1(system: &ValveSystem) -> Answer {
+    ValveSystemSolver::from(system.clone()).solve().into()
 }
-pub fn solve_2(input: &str) -> String {
-    let mut solver = ValveSystemSolver {
-        cache: HashMap::new(),
-        system: ValveSystem::from(input),
-    };
+pub fn solve_2(system: &ValveSystem) -> Answer {
+    ValveSystemSolver::from(system.clone())
+        .elephant_solve()
+        .into()
+}
+
+pub struct Day16;
 
-    solver.elephant_solve().to_string()
+impl Solution for Day16 {
+    type Model = ValveSystem;
+
+    fn name(&self) -> &'static str {
+        "Proboscidea Volcanium"
+    }
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        ValveSystem::from(input)
+    }
+
+    fn part1(&self, system: &Self::Model) -> Answer {
+        solve_1(system)
+    }
+
+    fn part2(&self, system: &Self::Model) -> Answer {
+        solve_2(system)
+    }
 }
@@ -3,8 +3,16 @@ use std::{
     fmt::Display,
 };
 
-#[derive(Clone)]
+use crate::{
+    answer::Answer,
+    extra::{visualize_colored, Palette, SvgStyle},
+    record::Recorder,
+    solution::Solution,
+};
+
+#[derive(Clone, Default)]
 pub enum Shape {
+    #[default]
     One,
     Two,
     Three,
@@ -12,12 +20,6 @@ pub enum Shape {
     Five,
 }
 
-impl Default for Shape {
-    fn default() -> Self {
-        Shape::One
-    }
-}
-
 #[derive(Clone, Default)]
 pub struct Rock {
     pub start: usize,
@@ -62,7 +64,7 @@ impl Rock {
             }
         }
 
-        return true;
+        true
     }
 
     fn can_apply_jet(&self, jet: Jet) -> bool {
@@ -95,7 +97,7 @@ impl Rock {
                 let index = (page - self.start) * 7 + x;
                 let carry_bit = 1 << x;
                 let carried = carry & carry_bit != 0;
-                let bottom_bit = (self.data[index] & 1);
+                let bottom_bit = self.data[index] & 1;
 
                 carry_next |= bottom_bit << x;
                 self.data[index] >>= 1;
@@ -110,11 +112,11 @@ impl Rock {
         if carry != 0 && self.start > 0 {
             let mut data = VecDeque::from(vec![0u64; 7]);
 
-            for carry_index in 0..7 {
+            for (carry_index, value) in data.iter_mut().enumerate() {
                 let carry_bit = 1 << carry_index;
                 let carried = carry & carry_bit != 0;
                 if carried {
-                    data[carry_index] = 1 << 63;
+                    *value = 1 << 63;
                 }
             }
 
@@ -128,16 +130,6 @@ impl Rock {
         true
     }
 
-    fn fill(&mut self, page: usize, amount: usize) {
-        for x in 0..7 {
-            let index = (page - self.start) * 7;
-
-            for bit_index in 0..amount {
-                self.data[index + x] |= 1 << bit_index;
-            }
-        }
-    }
-
     pub fn set(&mut self, x: u8, y: u64) {
         let page = y / 64;
         let page_y = y % 64;
@@ -161,7 +153,7 @@ impl Rock {
             };
         }
 
-        return '.';
+        '.'
     }
 
     pub fn move_jet(&mut self, jet: Jet) {
@@ -285,7 +277,7 @@ impl Rock {
         // #
         // #
         // #
-        piece.set(x, local_y + 0);
+        piece.set(x, local_y);
         piece.set(x, local_y + 1);
         piece.set(x, local_y + 2);
         piece.set(x, local_y + 3);
@@ -314,9 +306,9 @@ impl Rock {
 
         // ##
         // ##
-        piece.set(x + 0, local_y + 0);
-        piece.set(x + 1, local_y + 0);
-        piece.set(x + 0, local_y + 1);
+        piece.set(x, local_y);
+        piece.set(x + 1, local_y);
+        piece.set(x, local_y + 1);
         piece.set(x + 1, local_y + 1);
 
         piece
@@ -404,60 +396,6 @@ impl Rock {
             }
         }
     }
-
-    fn read(&self, x: u8, y: u64, bits: usize) -> u64 {
-        let page_start = y as usize / 64;
-        let page_end = if y % 64 != 0 {
-            page_start + 1
-        } else {
-            page_start
-        };
-
-        let start_offset = y % 64;
-        let end_offset = 63 - start_offset;
-
-        let mut data = self.data[page_start + x as usize] >> start_offset;
-        data |= self.data[page_end + x as usize] << end_offset;
-
-        let bit_shift = 64 - bits;
-
-        data <<= bit_shift;
-        data >>= bit_shift;
-
-        data
-    }
-}
-
-fn kmp_search(haystack: &[u64], needle: &[u64]) -> Option<usize> {
-    // Preprocess the needle to create the failure function
-    let mut failure_function = vec![0; needle.len()];
-    let mut j = 0;
-    for i in 1..needle.len() {
-        while j > 0 && needle[j] != needle[i] {
-            j = failure_function[j - 1];
-        }
-        if needle[j] == needle[i] {
-            j += 1;
-        }
-        failure_function[i] = j;
-    }
-
-    // Search for the needle in the haystack
-    let mut j = 0;
-    for i in 0..haystack.len() {
-        while j > 0 && haystack[i] != needle[j] {
-            j = failure_function[j - 1];
-        }
-        if haystack[i] == needle[j] {
-            j += 1;
-        }
-        if j == needle.len() {
-            return Some(i - j + 1);
-        }
-    }
-
-    // Return None if the needle is not found
-    None
 }
 
 impl Display for Rock {
@@ -548,7 +486,7 @@ impl RockFallSolver {
 
         if intersected {
             self.chamber.combine(&falling_backup);
-            return self.next_rock();
+            self.next_rock()
         }
     }
 
@@ -594,18 +532,45 @@ pub fn solver(input: &str) -> RockFallSolver {
     solver
 }
 
+/// The falling rock in bright yellow on top of the settled ones.
+pub const PALETTE: Palette = Palette(&[('@', "1;93"), ('#', "90")]);
+
+/// Rows of the chamber kept in a recording, counted from the highest rock down.
+const RECORDED_ROWS: usize = 40;
+
+pub fn recording_style() -> SvgStyle {
+    SvgStyle {
+        cell_size: 6,
+        ..SvgStyle::default()
+    }
+    .color('@', "#e2b659")
+    .color('#', "#6e6259")
+    .color('|', "#222")
+}
+
+/// The top rows of the chamber, where the rocks come to rest.
+pub struct ChamberTop<'a>(pub &'a RockFallSolver, pub usize);
+
+impl Display for ChamberTop<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self
+            .0
+            .to_string()
+            .lines()
+            .skip_while(|line| *line == "|.......|")
+            .take(self.1)
+        {
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Display for RockFallSolver {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for page in (self.chamber.start..=self.chamber.end).rev() {
             for y in (0..64).rev() {
-                let global_y: u64 = y + page as u64 * 64;
-
-                // if (global_y + 1) % 40 == 0 {
-                //     writeln!(f)?;
-                // }
-
-                // write!(f, "p={} y={:0>5} ", page, global_y)?;
-
                 write!(f, "|")?;
                 for x in 0..7 {
                     let falling_char = self.falling.as_char(page, x, y, true);
@@ -625,7 +590,9 @@ impl Display for RockFallSolver {
     }
 }
 
-pub fn print_solver_page(solver: &RockFallSolver, page: usize) {
+/// Visualizes one page of the chamber with the falling rock, like the other visualizations
+/// of `part`.
+pub fn visualize_solver_page(part: u8, solver: &RockFallSolver, page: usize) {
     let mut rock = Rock {
         data: VecDeque::new(),
         end: 0,
@@ -641,29 +608,73 @@ pub fn print_solver_page(solver: &RockFallSolver, page: usize) {
     let mut display_solver = solver.clone();
     display_solver.chamber = rock;
 
-    println!("{}", display_solver);
+    visualize_colored(17, part, &display_solver, &PALETTE);
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut solver = solver(input);
+pub fn solve_1(solver: &RockFallSolver) -> Answer {
+    let mut solver = solver.clone();
+    let mut recorder = Recorder::start(17, 1);
 
     for _ in 0..2022 {
         solver.step_rock();
+        recorder.push(&ChamberTop(&solver, RECORDED_ROWS));
     }
 
+    recorder.finish(
+        &ChamberTop(&solver, RECORDED_ROWS),
+        &PALETTE,
+        &recording_style(),
+    );
+    visualize_colored(17, 1, &ChamberTop(&solver, RECORDED_ROWS), &PALETTE);
+
     let solution = solver.chamber.top();
 
-    solution.to_string()
+    solution.into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut solver = solver(input);
+pub fn solve_2(solver: &RockFallSolver) -> Answer {
+    let mut solver = solver.clone();
+    let mut recorder = Recorder::start(17, 2);
 
     for _ in 0..10000 {
         solver.step_rock();
+        recorder.push(&ChamberTop(&solver, RECORDED_ROWS));
     }
 
+    recorder.finish(
+        &ChamberTop(&solver, RECORDED_ROWS),
+        &PALETTE,
+        &recording_style(),
+    );
+    visualize_colored(17, 2, &ChamberTop(&solver, RECORDED_ROWS), &PALETTE);
+
     let solution = solver.chamber.top();
 
-    solution.to_string()
+    solution.into()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Model = RockFallSolver;
+
+    fn name(&self) -> &'static str {
+        "Pyroclastic Flow"
+    }
+
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        solver(input)
+    }
+
+    fn part1(&self, solver: &Self::Model) -> Answer {
+        solve_1(solver)
+    }
+
+    fn part2(&self, solver: &Self::Model) -> Answer {
+        solve_2(solver)
+    }
 }
@@ -1,3 +1,5 @@
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(Clone, PartialEq)]
 pub struct Position {
     x: u64,
@@ -71,11 +73,7 @@ impl From<&str> for Position {
 pub fn count_connected_sides(mut cubes: Vec<Position>) -> usize {
     let mut count = 0;
 
-    loop {
-        let Some(current) = cubes.pop() else {
-            break;
-        };
-
+    while let Some(current) = cubes.pop() {
         for other in &cubes {
             if current.connects(other) {
                 count += 1;
@@ -106,7 +104,7 @@ fn bounds(cubes: &Vec<Position>) -> Position {
 
 pub fn fill_water(cubes: &Vec<Position>, start: Position) -> Vec<Position> {
     let mut filled = vec![];
-    let bounds = bounds(&cubes);
+    let bounds = bounds(cubes);
 
     let max_y = bounds.x + 1;
     let max_x = bounds.y + 1;
@@ -156,17 +154,42 @@ pub fn find_trapped_air(cubes: &Vec<Position>) -> Vec<Position> {
     trapped
 }
 
-pub fn solve_1(input: &str) -> String {
-    let cubes: Vec<Position> = input.lines().map(Position::from).collect();
+pub fn solve_1(cubes: &[Position]) -> Answer {
     let sides = cubes.len() * 6;
 
-    (sides - count_connected_sides(cubes) * 2).to_string()
+    (sides - count_connected_sides(cubes.to_vec()) * 2).into()
 }
-pub fn solve_2(input: &str) -> String {
-    let mut cubes: Vec<Position> = input.lines().map(Position::from).collect();
+pub fn solve_2(cubes: &[Position]) -> Answer {
+    let mut cubes = cubes.to_vec();
     let mut trapped_air = find_trapped_air(&cubes);
     cubes.append(&mut trapped_air);
 
     let sides = cubes.len() * 6;
-    (sides - count_connected_sides(cubes) * 2).to_string()
+    (sides - count_connected_sides(cubes) * 2).into()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Model = Vec<Position>;
+
+    fn name(&self) -> &'static str {
+        "Boiling Boulders"
+    }
+
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.lines().map(Position::from).collect()
+    }
+
+    fn part1(&self, cubes: &Self::Model) -> Answer {
+        solve_1(cubes)
+    }
+
+    fn part2(&self, cubes: &Self::Model) -> Answer {
+        solve_2(cubes)
+    }
 }
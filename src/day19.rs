@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(Clone)]
 enum Resource {
     Ore,
@@ -19,7 +21,7 @@ struct Cost {
 }
 
 #[derive(Clone, Hash, PartialEq, Eq)]
-struct Blueprint {
+pub struct Blueprint {
     id: u64,
     ore: Cost,
     clay: Cost,
@@ -51,17 +53,6 @@ impl Blueprint {
             Robot(Resource::Geode) => self.geode.clone(),
         }
     }
-
-    fn value(&self, robot: &Robot) -> u64 {
-        let Robot(resource) = robot;
-
-        match resource {
-            Resource::Ore => todo!(),
-            Resource::Clay => todo!(),
-            Resource::Obsidian => todo!(),
-            Resource::Geode => todo!(),
-        }
-    }
 }
 
 impl Simulation {
@@ -88,7 +79,7 @@ impl Simulation {
     }
 
     fn buy(&mut self, robot: &Robot) {
-        let cost = self.blueprint.cost(&robot);
+        let cost = self.blueprint.cost(robot);
 
         self.ore -= cost.ore;
         self.clay -= cost.clay;
@@ -102,7 +93,7 @@ impl Simulation {
         }
     }
 
-    fn run(self, mut cache: &mut HashMap<Self, u64>) -> u64 {
+    fn run(self, cache: &mut HashMap<Self, u64>) -> u64 {
         if self.time >= self.time_limit {
             cache.insert(self.clone(), self.geode);
             return self.geode;
@@ -116,7 +107,7 @@ impl Simulation {
             .clone()
             .step()
             .into_iter()
-            .map(|next| next.run(&mut cache))
+            .map(|next| next.run(cache))
             .max()
             .unwrap_or(0);
 
@@ -182,8 +173,6 @@ impl From<&str> for Blueprint {
             .take(7)
             .collect();
 
-        println!("{:?}", parsed);
-
         Self {
             id: parsed[0],
             ore: Cost {
@@ -210,11 +199,10 @@ impl From<&str> for Blueprint {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    let blueprints: Vec<Blueprint> = input.lines().map(Blueprint::from).collect();
-
+pub fn solve_1(blueprints: &[Blueprint]) -> Answer {
     let simulations: Vec<Simulation> = blueprints
-        .into_iter()
+        .iter()
+        .cloned()
         .map(Simulation::using_blueprint)
         .collect();
 
@@ -224,8 +212,34 @@ pub fn solve_1(input: &str) -> String {
         total_quality += simulation.quality_level();
     }
 
-    total_quality.to_string()
+    total_quality.into()
 }
-pub fn solve_2(input: &str) -> String {
+pub fn solve_2(_blueprints: &[Blueprint]) -> Answer {
     todo!()
 }
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Model = Vec<Blueprint>;
+
+    fn name(&self) -> &'static str {
+        "Not Enough Minerals"
+    }
+
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.lines().map(Blueprint::from).collect()
+    }
+
+    fn part1(&self, blueprints: &Self::Model) -> Answer {
+        solve_1(blueprints)
+    }
+
+    fn part2(&self, blueprints: &Self::Model) -> Answer {
+        solve_2(blueprints)
+    }
+}
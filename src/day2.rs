@@ -1,3 +1,5 @@
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(Clone)]
 pub enum Kind {
     Rock,
@@ -55,13 +57,12 @@ impl Match {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    input
-        .lines()
-        .map(Match::from)
-        .map(|m| m.score())
-        .sum::<u64>()
-        .to_string()
+pub fn parse_matches(input: &str) -> Vec<Match> {
+    input.lines().map(Match::from).collect()
+}
+
+pub fn solve_1(matches: &[Match]) -> Answer {
+    matches.iter().map(|m| m.score()).sum::<u64>().into()
 }
 
 pub enum Outcome {
@@ -94,10 +95,9 @@ impl Kind {
     }
 }
 
-pub fn solve_2(input: &str) -> String {
-    input
-        .lines()
-        .map(Match::from)
+pub fn solve_2(matches: &[Match]) -> Answer {
+    matches
+        .iter()
         .map(|m| {
             let mut adjusted = m.clone();
             let instruction = Outcome::from(&m.player_one);
@@ -106,5 +106,31 @@ pub fn solve_2(input: &str) -> String {
             adjusted.score()
         })
         .sum::<u64>()
-        .to_string()
+        .into()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Model = Vec<Match>;
+
+    fn name(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_matches(input)
+    }
+
+    fn part1(&self, matches: &Self::Model) -> Answer {
+        solve_1(matches)
+    }
+
+    fn part2(&self, matches: &Self::Model) -> Answer {
+        solve_2(matches)
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::{answer::Answer, solution::Solution};
+
 fn split_rucksack_line(line: &str) -> (&str, &str) {
     line.split_at(line.len() / 2)
 }
@@ -8,7 +10,7 @@ fn find_error(left: &str, right: &str) -> char {
     let l_set: HashSet<char> = HashSet::from_iter(left.chars());
     let r_set: HashSet<char> = HashSet::from_iter(right.chars());
 
-    let err = l_set.intersection(&r_set).into_iter().next();
+    let err = l_set.intersection(&r_set).next();
 
     err.cloned().expect("find backpack error")
 }
@@ -22,20 +24,20 @@ pub fn priority(char: &char) -> u32 {
         .expect("find item priority")
 }
 
-pub fn solve_1(input: &str) -> String {
-    let errors = input.lines().map(|line| {
+pub fn solve_1(rucksacks: &[String]) -> Answer {
+    let errors = rucksacks.iter().map(|line| {
         let (l, r) = split_rucksack_line(line);
         find_error(l, r)
     });
 
     let priorities = errors.map(|error| priority(&error));
 
-    priorities.sum::<u32>().to_string()
+    priorities.sum::<u32>().into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let charsets: Vec<HashSet<char>> = input
-        .lines()
+pub fn solve_2(rucksacks: &[String]) -> Answer {
+    let charsets: Vec<HashSet<char>> = rucksacks
+        .iter()
         .map(|line| HashSet::from_iter(line.chars()))
         .collect();
 
@@ -52,5 +54,31 @@ pub fn solve_2(input: &str) -> String {
             _ => unreachable!(),
         })
         .sum::<u32>()
-        .to_string()
+        .into()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Model = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.lines().map(str::to_string).collect()
+    }
+
+    fn part1(&self, rucksacks: &Self::Model) -> Answer {
+        solve_1(rucksacks)
+    }
+
+    fn part2(&self, rucksacks: &Self::Model) -> Answer {
+        solve_2(rucksacks)
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::{answer::Answer, solution::Solution};
+
 pub fn left_right_sets_from_line(line: &str) -> (HashSet<u32>, HashSet<u32>) {
     let (left, right) = line.split_once(',').expect("split section assignment");
     let (left_start, left_end): (u32, u32) = left
@@ -27,26 +29,48 @@ pub fn left_right_sets_from_line(line: &str) -> (HashSet<u32>, HashSet<u32>) {
     (left, right)
 }
 
-pub fn solve_1(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let (left, right) = left_right_sets_from_line(line);
+pub fn parse_assignments(input: &str) -> Vec<(HashSet<u32>, HashSet<u32>)> {
+    input.lines().map(left_right_sets_from_line).collect()
+}
 
-            usize::from(left.is_superset(&right) || left.is_subset(&right))
-        })
+pub fn solve_1(assignments: &[(HashSet<u32>, HashSet<u32>)]) -> Answer {
+    assignments
+        .iter()
+        .map(|(left, right)| usize::from(left.is_superset(right) || left.is_subset(right)))
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let (left, right) = left_right_sets_from_line(line);
-
-            usize::from(!left.is_disjoint(&right))
-        })
+pub fn solve_2(assignments: &[(HashSet<u32>, HashSet<u32>)]) -> Answer {
+    assignments
+        .iter()
+        .map(|(left, right)| usize::from(!left.is_disjoint(right)))
         .sum::<usize>()
-        .to_string()
+        .into()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Model = Vec<(HashSet<u32>, HashSet<u32>)>;
+
+    fn name(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_assignments(input)
+    }
+
+    fn part1(&self, assignments: &Self::Model) -> Answer {
+        solve_1(assignments)
+    }
+
+    fn part2(&self, assignments: &Self::Model) -> Answer {
+        solve_2(assignments)
+    }
 }
@@ -1,5 +1,21 @@
 use std::{collections::VecDeque, vec};
 
+use crate::{answer::Answer, solution::Solution};
+
+pub struct Procedure {
+    pub crates: Vec<VecDeque<char>>,
+    pub moves: Vec<(usize, usize, usize)>,
+}
+
+impl From<&str> for Procedure {
+    fn from(input: &str) -> Self {
+        Self {
+            crates: read_crates(input),
+            moves: read_moves(input),
+        }
+    }
+}
+
 pub fn read_crate_line(line: &str) -> Vec<Option<char>> {
     let char_vector: Vec<char> = line.chars().collect();
     let chunks = char_vector.chunks(4);
@@ -25,7 +41,7 @@ pub fn read_crates(input: &str) -> Vec<VecDeque<char>> {
         if line.starts_with(" 1") {
             number_of_crates = line
                 .split(' ')
-                .last()
+                .next_back()
                 .expect("read last crate number")
                 .parse()
                 .expect("parse last crate number");
@@ -118,15 +134,14 @@ pub fn crates_front_string(crates: Vec<VecDeque<char>>) -> String {
     output
 }
 
-pub fn solve_1(input: &str) -> String {
-    let mut crates = read_crates(input);
-    let move_instructions = read_moves(input);
+pub fn solve_1(procedure: &Procedure) -> Answer {
+    let mut crates = procedure.crates.clone();
 
-    for move_instruction in move_instructions {
-        crates = perform_move(crates, move_instruction);
+    for move_instruction in &procedure.moves {
+        crates = perform_move(crates, *move_instruction);
     }
 
-    crates_front_string(crates)
+    crates_front_string(crates).into()
 }
 
 pub fn perform_move_2(
@@ -165,13 +180,38 @@ pub fn perform_move_2(
     new
 }
 
-pub fn solve_2(input: &str) -> String {
-    let mut crates = read_crates(input);
-    let move_instructions = read_moves(input);
+pub fn solve_2(procedure: &Procedure) -> Answer {
+    let mut crates = procedure.crates.clone();
+
+    for move_instruction in &procedure.moves {
+        crates = perform_move_2(crates, *move_instruction);
+    }
+
+    crates_front_string(crates).into()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Model = Procedure;
+
+    fn name(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
 
-    for move_instruction in move_instructions {
-        crates = perform_move_2(crates, move_instruction);
+    fn parse(&self, input: &str) -> Self::Model {
+        Procedure::from(input)
     }
 
-    crates_front_string(crates)
+    fn part1(&self, procedure: &Self::Model) -> Answer {
+        solve_1(procedure)
+    }
+
+    fn part2(&self, procedure: &Self::Model) -> Answer {
+        solve_2(procedure)
+    }
 }
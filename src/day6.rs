@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
-pub fn solve_1(input: &str) -> String {
-    let found = input
-        .chars()
-        .collect::<Vec<char>>()
+use crate::{answer::Answer, solution::Solution};
+
+pub fn solve_1(signal: &[char]) -> Answer {
+    let found = signal
         .windows(4)
         .map(|chars| {
             let mut set: HashSet<char> = HashSet::new();
@@ -18,13 +18,11 @@ pub fn solve_1(input: &str) -> String {
         .find(|(_, set)| set.len() == 4)
         .expect("solution");
 
-    (found.0 + 4).to_string()
+    (found.0 + 4).into()
 }
 
-pub fn solve_2(input: &str) -> String {
-    let found = input
-        .chars()
-        .collect::<Vec<char>>()
+pub fn solve_2(signal: &[char]) -> Answer {
+    let found = signal
         .windows(14)
         .map(|chars| {
             let mut set = HashSet::<char>::new();
@@ -39,5 +37,31 @@ pub fn solve_2(input: &str) -> String {
         .find(|(_, set)| set.len() == 14)
         .expect("solution");
 
-    (found.0 + 14).to_string()
+    (found.0 + 14).into()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Model = Vec<char>;
+
+    fn name(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        input.chars().collect()
+    }
+
+    fn part1(&self, signal: &Self::Model) -> Answer {
+        solve_1(signal)
+    }
+
+    fn part2(&self, signal: &Self::Model) -> Answer {
+        solve_2(signal)
+    }
 }
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, path::PathBuf};
 
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(Debug)]
 pub struct Listing {
     path: PathBuf,
@@ -174,16 +176,14 @@ impl From<&str> for Directory {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    let root = Directory::from(input);
-
+pub fn solve_1(root: &Directory) -> Answer {
     let mut sum = 0;
 
-    for (_, size) in directories_within_limit(100000, &root) {
+    for (_, size) in directories_within_limit(100000, root) {
         sum += size;
     }
 
-    sum.to_string()
+    sum.into()
 }
 
 pub fn find_smallest_needed_delete(
@@ -212,6 +212,32 @@ pub fn find_smallest_needed_delete(
     best.expect("the best")
 }
 
-pub fn solve_2(input: &str) -> String {
-    find_smallest_needed_delete(Directory::from(input), 70000000, 30000000).to_string()
+pub fn solve_2(root: &Directory) -> Answer {
+    find_smallest_needed_delete(root.clone(), 70000000, 30000000).into()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Model = Directory;
+
+    fn name(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        Directory::from(input)
+    }
+
+    fn part1(&self, root: &Self::Model) -> Answer {
+        solve_1(root)
+    }
+
+    fn part2(&self, root: &Self::Model) -> Answer {
+        solve_2(root)
+    }
 }
@@ -1,5 +1,7 @@
 use std::ops::{Deref, Mul};
 
+use crate::{answer::Answer, solution::Solution};
+
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub struct Coord {
     pub x: usize,
@@ -84,8 +86,7 @@ impl TreeGrid {
     }
 }
 
-pub fn solve_1(input: &str) -> String {
-    let scan = TreeGrid::from(input);
+pub fn solve_1(scan: &TreeGrid) -> Answer {
     let width = scan.width;
     let height = scan.height;
 
@@ -99,7 +100,7 @@ pub fn solve_1(input: &str) -> String {
         }
     }
 
-    visible.to_string()
+    visible.into()
 }
 
 pub enum ViewDirection {
@@ -158,9 +159,7 @@ impl TreeGrid {
     }
 }
 
-pub fn solve_2(input: &str) -> String {
-    let scan = TreeGrid::from(input);
-
+pub fn solve_2(scan: &TreeGrid) -> Answer {
     let mut max = 0;
 
     let width = scan.width;
@@ -176,5 +175,39 @@ pub fn solve_2(input: &str) -> String {
         }
     }
 
-    max.to_string()
+    max.into()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Model = TreeGrid;
+
+    fn name(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        TreeGrid::from(input)
+    }
+
+    fn part1(&self, scan: &Self::Model) -> Answer {
+        solve_1(scan)
+    }
+
+    fn part2(&self, scan: &Self::Model) -> Answer {
+        solve_2(scan)
+    }
+}
+
+#[test]
+fn scenic_score_test() {
+    let scan = TreeGrid::from(include_str!("../example_input/day8.txt"));
+
+    assert_eq!(scan.scenic_score(&Coord { x: 2, y: 1 }), 4);
+    assert_eq!(scan.scenic_score(&Coord { x: 0, y: 0 }), 0);
 }
@@ -1,6 +1,11 @@
 use std::{collections::HashSet, fmt::Display, iter::repeat};
 
-use crate::extra::visualize;
+use crate::{
+    answer::Answer,
+    extra::{visualize_colored, Palette, SvgStyle},
+    record::Recorder,
+    solution::Solution,
+};
 
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub struct Position {
@@ -119,17 +124,48 @@ impl RopeSimulation {
         self.bounding_box.upper.y = self.bounding_box.upper.x.max(head.y);
     }
 
-    pub fn perform_moves(&mut self, moves: Vec<Direction>) -> usize {
+    /// Moves the rope, with a frame per move.
+    pub fn perform_moves(&mut self, moves: Vec<Direction>, recorder: &mut Recorder) -> usize {
         self.mark_tail();
 
         for instruction in moves {
             self.perform_move(instruction);
+            recorder.push(self);
         }
 
         self.tail_markers.len()
     }
 }
 
+/// The head stands out from the knots behind it, the visited cells are faint.
+pub const PALETTE: Palette = Palette(&[
+    ('H', "1;31"),
+    ('T', "33"),
+    ('1', "33"),
+    ('2', "33"),
+    ('3', "33"),
+    ('4', "33"),
+    ('5', "33"),
+    ('6', "33"),
+    ('7', "33"),
+    ('8', "33"),
+    ('9', "33"),
+    ('#', "36"),
+]);
+
+pub fn recording_style() -> SvgStyle {
+    let style = SvgStyle {
+        cell_size: 3,
+        ..SvgStyle::default()
+    }
+    .color('H', "#d9453d")
+    .color('#', "#8fb8de");
+
+    "T123456789"
+        .chars()
+        .fold(style, |style, knot| style.color(knot, "#e2b659"))
+}
+
 impl Display for RopeSimulation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let bounding_box = self.bounding_box.clone();
@@ -181,22 +217,52 @@ pub fn parse_directions(input: &str) -> Vec<Direction> {
     output
 }
 
-pub fn solve_1(input: &str) -> String {
+pub fn solve_1(directions: &[Direction]) -> Answer {
     let mut simulation = RopeSimulation::new(2);
 
-    let moves = simulation.perform_moves(parse_directions(input));
+    let mut recorder = Recorder::start(9, 1);
+    let moves = simulation.perform_moves(directions.to_vec(), &mut recorder);
+    recorder.finish(&simulation, &PALETTE, &recording_style());
 
-    visualize(9, 1, &simulation);
+    visualize_colored(9, 1, &simulation, &PALETTE);
 
-    moves.to_string()
+    moves.into()
 }
 
-pub fn solve_2(input: &str) -> String {
+pub fn solve_2(directions: &[Direction]) -> Answer {
     let mut simulation = RopeSimulation::new(10);
 
-    let moves = simulation.perform_moves(parse_directions(input));
+    let mut recorder = Recorder::start(9, 2);
+    let moves = simulation.perform_moves(directions.to_vec(), &mut recorder);
+    recorder.finish(&simulation, &PALETTE, &recording_style());
 
-    visualize(9, 2, &simulation);
+    visualize_colored(9, 2, &simulation, &PALETTE);
 
-    moves.to_string()
+    moves.into()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Model = Vec<Direction>;
+
+    fn name(&self) -> &'static str {
+        "Rope Bridge"
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse(&self, input: &str) -> Self::Model {
+        parse_directions(input)
+    }
+
+    fn part1(&self, directions: &Self::Model) -> Answer {
+        solve_1(directions)
+    }
+
+    fn part2(&self, directions: &Self::Model) -> Answer {
+        solve_2(directions)
+    }
 }
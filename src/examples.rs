@@ -0,0 +1,139 @@
+//! Example tests. Every `example_input/<name>.expected` file is a test case, `build.rs`
+//! generates a test function for each of them that calls `check`. Watch mode reads the same
+//! files to solve the examples.
+//!
+//! ```text
+//! # Comments and blank lines are ignored
+//! input: day17.txt        # optional, defaults to <name>.txt
+//! ignore: reason          # optional, marks the test as ignored
+//! row: 10                 # any other key is a parameter for Solution::configure
+//! part1: 26
+//! part2: 56000011         # multi-line answers use \n like answers.txt
+//! ```
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{answer::Answer, answers::unescape, solution::Params};
+#[cfg(test)]
+use {crate::registry, std::path::PathBuf};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub params: Params,
+    pub expected: BTreeMap<u8, Answer>,
+}
+
+impl TryFrom<&str> for Example {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut example = Example::default();
+
+        for (number, line) in value.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(format!("line {}: expected 'key: value'", number + 1));
+            };
+
+            let value = value.strip_prefix(' ').unwrap_or(value);
+
+            match key.trim() {
+                "input" => example.input = Some(value.trim().to_string()),
+                "ignore" => {}
+                // Trimmed like the answers file, so both read answers the same way.
+                "part1" => {
+                    _ = example
+                        .expected
+                        .insert(1, Answer::parse(unescape(value).trim_end()))
+                }
+                "part2" => {
+                    _ = example
+                        .expected
+                        .insert(2, Answer::parse(unescape(value).trim_end()))
+                }
+                key => {
+                    _ = example
+                        .params
+                        .insert(key.to_string(), value.trim().to_string())
+                }
+            }
+        }
+
+        Ok(example)
+    }
+}
+
+impl Example {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| Example::try_from(content.as_str()))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+#[cfg(test)]
+fn example_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example_input")
+}
+
+/// Solves the example `name` and compares the answers with `<name>.expected`.
+#[cfg(test)]
+pub fn check(name: &str) {
+    let path = example_dir().join(format!("{}.expected", name));
+    let example = Example::load(&path).unwrap_or_else(|err| panic!("{}", err));
+
+    let day: u8 = name
+        .trim_start_matches("day")
+        .split(|char: char| !char.is_ascii_digit())
+        .next()
+        .and_then(|day| day.parse().ok())
+        .unwrap_or_else(|| panic!("{}: name should start with dayN", path.display()));
+
+    let solution = registry::find(day).unwrap_or_else(|| panic!("day {} has no solution", day));
+
+    let input_name = example.input.unwrap_or_else(|| format!("{}.txt", name));
+    let input = fs::read_to_string(example_dir().join(&input_name)).expect("read example input");
+
+    let mut model = solution.parse(&input);
+
+    solution
+        .configure(&mut model, &example.params)
+        .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+
+    for (part, expected) in &example.expected {
+        let answer = solution.solve(*part, &model);
+
+        assert!(
+            answer.matches(expected),
+            "{} part {}: expected {}, got {}",
+            name,
+            part,
+            expected,
+            answer
+        );
+    }
+}
+
+#[cfg(test)]
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+#[test]
+fn parses_expected_answers() {
+    let example =
+        Example::try_from("# comment\ninput: day9.txt\nrow: 10\npart1: 26 \npart2: a\\nb\n")
+            .unwrap();
+
+    assert_eq!(example.input.as_deref(), Some("day9.txt"));
+    assert_eq!(example.params.get("row").map(String::as_str), Some("10"));
+    assert_eq!(example.expected.get(&1), Some(&Answer::Integer(26)));
+    assert_eq!(
+        example.expected.get(&2),
+        Some(&Answer::Text("a\nb".to_string()))
+    );
+    assert!(Example::try_from("part1 26").is_err());
+}
@@ -1,6 +1,15 @@
-use std::{env, fmt::Display, path::PathBuf};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::{Display, Write},
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use crate::day7::Directory;
+use crate::{day7::Directory, report::xml_escape};
 
 pub struct GraphMetadata {
     pub x: i64,
@@ -13,33 +22,237 @@ pub struct GraphMetadata {
     pub legend_x_width: u32,
 }
 
+/// How [`ChristmasGraph::svg`] draws a graph.
+#[derive(Debug, Clone)]
+pub struct SvgStyle {
+    /// Side of a cell in pixels.
+    pub cell_size: u32,
+    /// Fill per cell character, in any SVG color syntax.
+    pub colors: BTreeMap<char, String>,
+    /// Fill for characters without a color of their own.
+    pub default_color: String,
+    pub background: String,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: 10,
+            colors: BTreeMap::new(),
+            default_color: "#444".to_string(),
+            background: "#fff".to_string(),
+        }
+    }
+}
+
+impl SvgStyle {
+    pub fn color(mut self, cell: char, color: &str) -> Self {
+        self.colors.insert(cell, color.to_string());
+        self
+    }
+}
+
+const SVG_FONT_SIZE: i64 = 10;
+/// Roughly the advance of a monospace character at [`SVG_FONT_SIZE`].
+const SVG_CHAR_WIDTH: i64 = 6;
+
+/// A rectangle of graph coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: i64,
+    pub y: i64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    pub fn intersection(&self, other: &Region) -> Region {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let end_x = (self.x + self.width as i64).min(other.x + other.width as i64);
+        let end_y = (self.y + self.height as i64).min(other.y + other.height as i64);
+
+        Region {
+            x,
+            y,
+            width: (end_x - x).max(0) as u32,
+            height: (end_y - y).max(0) as u32,
+        }
+    }
+
+    /// Splits the region into tiles of at most `size` cells, row by row.
+    pub fn tiles(&self, size: Option<(u32, u32)>) -> Vec<Region> {
+        let Some((width, height)) = size else {
+            return vec![*self];
+        };
+
+        let mut tiles = vec![];
+
+        for y in (0..self.height).step_by(height.max(1) as usize) {
+            for x in (0..self.width).step_by(width.max(1) as usize) {
+                tiles.push(Region {
+                    x: self.x + x as i64,
+                    y: self.y + y as i64,
+                    width: width.min(self.width - x),
+                    height: height.min(self.height - y),
+                });
+            }
+        }
+
+        tiles
+    }
+}
+
+impl From<&GraphMetadata> for Region {
+    fn from(metadata: &GraphMetadata) -> Self {
+        Region {
+            x: metadata.x,
+            y: metadata.y,
+            width: metadata.width,
+            height: metadata.height,
+        }
+    }
+}
+
+/// Parses `X,Y,WIDTHxHEIGHT`, e.g. `480,0,40x30`.
+impl TryFrom<&str> for Region {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let parsed = value.split_once(',').and_then(|(x, rest)| {
+            let (y, size) = rest.split_once(',')?;
+            let (width, height) = parse_size(size)?;
+
+            Some(Region {
+                x: x.trim().parse().ok()?,
+                y: y.trim().parse().ok()?,
+                width,
+                height,
+            })
+        });
+
+        parsed.ok_or_else(|| format!("invalid region '{}', expected X,Y,WIDTHxHEIGHT", value))
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "x {}..{}, y {}..{}",
+            self.x,
+            self.x + self.width as i64,
+            self.y,
+            self.y + self.height as i64
+        )
+    }
+}
+
+/// Parses `WIDTHxHEIGHT`, e.g. `60x40`.
+pub fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let (width, height) = value.trim().split_once('x')?;
+
+    match (width.parse().ok()?, height.parse().ok()?) {
+        (0, _) | (_, 0) => None,
+        size => Some(size),
+    }
+}
+
+/// Which part of a [`ChristmasGraph`] is rendered, and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// Only render this region, `None` renders the whole graph.
+    pub crop: Option<Region>,
+    /// Show every `scale`th cell in both directions.
+    pub scale: u32,
+    /// Split the output into tiles of at most this many rendered cells.
+    pub tile: Option<(u32, u32)>,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            crop: None,
+            scale: 1,
+            tile: None,
+        }
+    }
+}
+
+/// The coordinate in the block of `scale` values from `start`, cut off at `end`, that has a
+/// legend every `step`, if any.
+fn legend_in(start: i64, end: i64, scale: i64, step: u32) -> Option<i64> {
+    (start..(start + scale).min(end)).find(|value| value % step as i64 == 0)
+}
+
+/// The first value in the block of `scale` by `scale` cells from `(x, y)`, cut off at the end
+/// of the region.
+fn block_value<T>(
+    graph: &T,
+    (x, y): (i64, i64),
+    scale: i64,
+    (end_x, end_y): (i64, i64),
+) -> Option<char>
+where
+    T: ChristmasGraph + ?Sized,
+{
+    (y..(y + scale).min(end_y))
+        .flat_map(|y| (x..(x + scale).min(end_x)).map(move |x| (x, y)))
+        .find_map(|(x, y)| graph.graph_value(x, y))
+}
+
+/// A region of a graph as rendered by [`ChristmasGraph::fmt_region`].
+struct Rendered<'a, T: ?Sized>(&'a T, &'a Region, u32);
+
+impl<T> Display for Rendered<'_, T>
+where
+    T: ChristmasGraph + ?Sized,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_region(f, self.1, self.2)
+    }
+}
+
 pub trait ChristmasGraph {
     fn as_graph_metadata(&self) -> GraphMetadata;
     fn graph_legend_x(&self, value: i64) -> Vec<char>;
     fn graph_legend_y(&self, value: i64) -> Vec<char>;
     fn graph_value(&self, x: i64, y: i64) -> Option<char>;
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metadata = self.as_graph_metadata();
+
+        self.fmt_region(f, &Region::from(&metadata), 1)
+    }
+
+    /// Renders the cells of `region` like [`ChristmasGraph::fmt`], showing every `scale`th
+    /// cell in both directions. A scaled down cell shows the first value in the block it
+    /// stands for, and gets a legend if any coordinate in the block has one.
+    fn fmt_region(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        region: &Region,
+        scale: u32,
+    ) -> std::fmt::Result {
         let graph_metadata = self.as_graph_metadata();
 
-        let graph_x = graph_metadata.x;
-        let graph_y = graph_metadata.y;
-        let width = graph_metadata.width;
-        let height = graph_metadata.height;
-        let end_x = graph_x + width as i64;
-        let end_y = graph_y + height as i64;
+        let scale = scale.max(1) as i64;
+        let end_x = region.x + region.width as i64;
+        let end_y = region.y + region.height as i64;
         let step_x = graph_metadata.legend_step_x;
         let step_y = graph_metadata.legend_step_y;
 
         let legend_x_width = graph_metadata.legend_x_width;
         let legend_y_width = graph_metadata.legend_y_width;
 
+        let columns: Vec<i64> = (region.x..end_x).step_by(scale as usize).collect();
+
         for y in 0..legend_x_width as usize {
             write!(f, "{:>width$}", "", width = legend_y_width as usize + 1)?;
-            for x in graph_x..end_x {
-                if x % step_x as i64 == 0 {
+            for (index, x) in columns.iter().enumerate() {
+                if let Some(x) = legend_in(*x, end_x, scale, step_x) {
                     let legend = *self.graph_legend_x(x).get(y).unwrap_or(&' ');
                     write!(f, "{} ", legend)?;
-                } else if x + 1 - graph_x != width as i64 {
+                } else if index + 1 != columns.len() {
                     write!(f, "  ")?;
                 }
             }
@@ -47,11 +260,11 @@ pub trait ChristmasGraph {
             writeln!(f)?;
         }
 
-        for y in graph_y..end_y {
-            if y % step_y as i64 == 0 {
+        for y in (region.y..end_y).step_by(scale as usize) {
+            if let Some(y) = legend_in(y, end_y, scale, step_y) {
                 let legend = self.graph_legend_y(y);
 
-                for _ in 0..(legend_y_width as usize - legend.len()) {
+                for _ in 0..(legend_y_width as usize).saturating_sub(legend.len()) {
                     write!(f, " ")?;
                 }
 
@@ -64,13 +277,13 @@ pub trait ChristmasGraph {
 
             write!(f, " ")?;
 
-            for x in graph_x..end_x {
-                if let Some(value) = self.graph_value(x, y) {
+            for (index, x) in columns.iter().enumerate() {
+                if let Some(value) = block_value(self, (*x, y), scale, (end_x, end_y)) {
                     value.fmt(f)?;
                 } else {
                     write!(f, ".")?;
                 }
-                if x + 1 - graph_x != width as i64 {
+                if index + 1 != columns.len() {
                     write!(f, " ")?;
                 }
             }
@@ -79,6 +292,186 @@ pub trait ChristmasGraph {
 
         Ok(())
     }
+
+    /// Renders the part of the graph the viewport selects, one tile after the other with
+    /// their coordinates in between.
+    fn render(&self, viewport: &Viewport) -> String {
+        let graph = Region::from(&self.as_graph_metadata());
+
+        let region = match &viewport.crop {
+            Some(crop) => crop.intersection(&graph),
+            None => graph,
+        };
+
+        let scale = viewport.scale.max(1);
+        let tiles = region.tiles(
+            viewport
+                .tile
+                .map(|(width, height)| (width * scale, height * scale)),
+        );
+        let mut output = String::new();
+
+        for tile in &tiles {
+            if tiles.len() > 1 {
+                writeln!(output, "{}", tile).unwrap();
+            }
+
+            write!(output, "{}", Rendered(self, tile, scale)).unwrap();
+        }
+
+        output
+    }
+
+    /// Draws the part of the graph the viewport selects as an SVG image, with the same legends
+    /// as [`ChristmasGraph::fmt`] and a key of the cell characters below it. The image isn't
+    /// split into tiles.
+    fn svg(&self, style: &SvgStyle, viewport: &Viewport) -> String {
+        let metadata = self.as_graph_metadata();
+        let graph = Region::from(&metadata);
+        let region = match &viewport.crop {
+            Some(crop) => crop.intersection(&graph),
+            None => graph,
+        };
+
+        let cell = style.cell_size as i64;
+        let scale = viewport.scale.max(1) as i64;
+        let end = (
+            region.x + region.width as i64,
+            region.y + region.height as i64,
+        );
+        let columns: Vec<i64> = (region.x..end.0).step_by(scale as usize).collect();
+        let rows: Vec<i64> = (region.y..end.1).step_by(scale as usize).collect();
+
+        let left = (metadata.legend_y_width as i64 + 1) * SVG_CHAR_WIDTH;
+        let top = SVG_FONT_SIZE * 2;
+        let graph_width = columns.len() as i64 * cell;
+        let graph_height = rows.len() as i64 * cell;
+
+        let mut cells = String::new();
+        let mut present = BTreeSet::new();
+
+        for (row, y) in rows.iter().enumerate() {
+            for (column, x) in columns.iter().enumerate() {
+                let Some(value) = block_value(self, (*x, *y), scale, end) else {
+                    continue;
+                };
+
+                present.insert(value);
+
+                writeln!(
+                    cells,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    left + column as i64 * cell,
+                    top + row as i64 * cell,
+                    cell,
+                    cell,
+                    style.colors.get(&value).unwrap_or(&style.default_color)
+                )
+                .unwrap();
+            }
+        }
+
+        let mut legends = String::new();
+
+        for (column, x) in columns.iter().enumerate() {
+            if let Some(x) = legend_in(*x, end.0, scale, metadata.legend_step_x) {
+                writeln!(
+                    legends,
+                    r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                    left + column as i64 * cell + cell / 2,
+                    top - SVG_FONT_SIZE / 2,
+                    xml_escape(&self.graph_legend_x(x).into_iter().collect::<String>())
+                )
+                .unwrap();
+            }
+        }
+
+        for (row, y) in rows.iter().enumerate() {
+            if let Some(y) = legend_in(*y, end.1, scale, metadata.legend_step_y) {
+                writeln!(
+                    legends,
+                    r#"<text x="{}" y="{}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+                    left - SVG_CHAR_WIDTH,
+                    top + row as i64 * cell + cell / 2,
+                    xml_escape(&self.graph_legend_y(y).into_iter().collect::<String>())
+                )
+                .unwrap();
+            }
+        }
+
+        let key_y = top + graph_height + SVG_FONT_SIZE;
+        let key_spacing = SVG_FONT_SIZE + 4 * SVG_CHAR_WIDTH;
+
+        for (index, value) in present.iter().enumerate() {
+            let x = left + index as i64 * key_spacing;
+
+            writeln!(
+                legends,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/><text x="{}" y="{}" dominant-baseline="middle">{}</text>"#,
+                x,
+                key_y,
+                SVG_FONT_SIZE,
+                SVG_FONT_SIZE,
+                style.colors.get(value).unwrap_or(&style.default_color),
+                x + SVG_FONT_SIZE + SVG_CHAR_WIDTH / 2,
+                key_y + SVG_FONT_SIZE / 2,
+                xml_escape(&value.to_string())
+            )
+            .unwrap();
+        }
+
+        let width = left + graph_width + SVG_CHAR_WIDTH;
+        let height = key_y + SVG_FONT_SIZE * 2;
+
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" "#,
+                r#"viewBox="0 0 {width} {height}" font-family="monospace" font-size="{font}">"#,
+                "\n",
+                r#"<rect width="100%" height="100%" fill="{background}"/>"#,
+                "\n",
+                r#"<g shape-rendering="crispEdges">"#,
+                "\n{cells}</g>\n{legends}</svg>\n"
+            ),
+            width = width,
+            height = height,
+            font = SVG_FONT_SIZE,
+            background = style.background,
+            cells = cells,
+            legends = legends,
+        )
+    }
+
+    fn save_svg(&self, path: &Path, style: &SvgStyle, viewport: &Viewport) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("{}: {}", parent.display(), err))?;
+        }
+
+        fs::write(path, self.svg(style, viewport))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+/// One character per cell of a graph without legends, `.` for empty cells. Small enough
+/// for frames of a recording.
+pub struct Cells<'a, T>(pub &'a T);
+
+impl<T> Display for Cells<'_, T>
+where
+    T: ChristmasGraph,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metadata = self.0.as_graph_metadata();
+
+        for y in metadata.y..metadata.y + metadata.height as i64 {
+            for x in metadata.x..metadata.x + metadata.width as i64 {
+                write!(f, "{}", self.0.graph_value(x, y).unwrap_or('.'))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Directory {
@@ -109,46 +502,438 @@ impl Display for Directory {
     }
 }
 
-pub fn visualize<T>(day: u8, part: u8, value: &T)
-where
-    T: Display,
-{
-    fn perform_print<T>(_day: u8, _part: u8, value: &T)
-    where
-        T: Display,
-    {
-        // let line = format!("Day {day:} part {part:}");
-        // let line = format!("{line:-^80}");
-        // println!("{line:}");
-        println!("{value:}")
-    }
+/// When printed visualizations are colored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Only when stderr, where visualizations are printed, is a terminal and `NO_COLOR`
+    /// isn't set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl TryFrom<&str> for ColorMode {
+    type Error = String;
 
-    if let Ok(visualize) = env::var("VISUALIZE") {
-        if visualize != "true" && visualize != "1" {
-            return;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            _ => Err(format!(
+                "invalid color mode '{}', expected auto, always or never",
+                value
+            )),
         }
     }
+}
 
-    match env::var("DAY") {
-        Ok(day_str) => {
-            if day_str.parse::<u8>().unwrap() != day {
-                return;
-            }
+impl Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            ColorMode::Auto => "auto",
+            ColorMode::Always => "always",
+            ColorMode::Never => "never",
+        })
+    }
+}
+
+/// ANSI SGR codes per cell character of a visualization, e.g. `('#', "90")` for gray rock.
+/// Characters without a code keep the terminal's color.
+pub struct Palette(pub &'static [(char, &'static str)]);
+
+impl Palette {
+    fn code(&self, char: char) -> Option<&'static str> {
+        self.0
+            .iter()
+            .find(|(cell, _)| *cell == char)
+            .map(|(_, code)| *code)
+    }
+
+    /// Wraps the characters of `text` in their colors. Neighbours of the same color share
+    /// one escape sequence.
+    pub fn paint(&self, text: &str) -> String {
+        let mut output = String::new();
+        let mut current = None;
 
-            match env::var("PART") {
-                Ok(part_str) => {
-                    if part_str.parse::<u8>().unwrap() != part {
-                        return;
-                    }
-                    perform_print(day, part, value)
+        for char in text.chars() {
+            let code = match char {
+                '\n' => None,
+                _ => self.code(char),
+            };
+
+            if code != current {
+                if current.is_some() {
+                    output += "\x1b[0m";
+                }
+                if let Some(code) = code {
+                    write!(output, "\x1b[{}m", code).unwrap();
                 }
-                Err(_) => perform_print(day, part, value),
+                current = code;
             }
+
+            output.push(char);
+        }
+
+        if current.is_some() {
+            output += "\x1b[0m";
         }
-        Err(_) => {
-            if cfg!(test) {
-                perform_print(day, part, value)
+
+        output
+    }
+}
+
+/// Where visualizations go, see [`Visuals::sink`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Sink {
+    #[default]
+    Disabled,
+    /// Printed to stderr, so they stay out of the report, colored when color output is on.
+    Stderr,
+    /// One file per frame, `DIR/dayNptM-FRAME.txt` counting from 1.
+    Files(PathBuf),
+    /// Kept in memory for whoever holds on to the frames.
+    Buffer(Frames),
+}
+
+type FramesByPart = BTreeMap<(u8, u8), Vec<String>>;
+
+/// Visualizations per day and part, oldest first, shared by all clones.
+#[derive(Debug, Clone, Default)]
+pub struct Frames(Arc<Mutex<FramesByPart>>);
+
+impl Frames {
+    fn push(&self, day: u8, part: u8, frame: String) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry((day, part))
+            .or_default()
+            .push(frame);
+    }
+
+    /// The visualizations of a part so far, oldest first.
+    pub fn get(&self, day: u8, part: u8) -> Vec<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(&(day, part))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The same frames, not just equal ones.
+impl PartialEq for Frames {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Frames {}
+
+/// Where and how solutions visualize. The runner passes them to every solution it runs, see
+/// `Settings::visuals`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Visuals {
+    pub sink: Sink,
+    /// Whether printed visualizations are colored, see [`ColorMode::enabled`].
+    pub color: bool,
+    /// How [`visualize_graph`] and [`visualize_svg`] render graphs.
+    pub viewport: Viewport,
+    /// Where [`visualize_svg`] writes its images, `None` skips them.
+    pub svg_dir: Option<PathBuf>,
+    /// Where recordings are saved, `None` turns recording off.
+    pub record_dir: Option<PathBuf>,
+}
+
+struct Context {
+    visuals: Visuals,
+    /// Frames written per day and part, for [`Sink::Files`].
+    counts: BTreeMap<(u8, u8), usize>,
+}
+
+thread_local! {
+    /// How the solution running on this thread visualizes, see [`with_visuals`].
+    static CONTEXT: RefCell<Context> = const {
+        RefCell::new(Context {
+            visuals: Visuals {
+                sink: Sink::Disabled,
+                color: false,
+                viewport: Viewport {
+                    crop: None,
+                    scale: 1,
+                    tile: None,
+                },
+                svg_dir: None,
+                record_dir: None,
+            },
+            counts: BTreeMap::new(),
+        })
+    };
+}
+
+/// Runs `f` with the visualizations it makes on this thread following `visuals`. The runner
+/// does this for every solution, so solutions don't need to know where their output goes.
+pub fn with_visuals<T>(visuals: Visuals, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous context back, even when `f` panics.
+    struct Restore(Option<Context>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                CONTEXT.with(|context| *context.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let previous = CONTEXT.with(|context| {
+        context.replace(Context {
+            visuals,
+            counts: BTreeMap::new(),
+        })
+    });
+    let _restore = Restore(Some(previous));
+
+    f()
+}
+
+/// Reads the visuals of the solution running on this thread.
+pub fn current_visuals<T>(f: impl FnOnce(&Visuals) -> T) -> T {
+    CONTEXT.with(|context| f(&context.borrow().visuals))
+}
+
+impl ColorMode {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorMode::Auto => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// Colors `text` with the palette if the current [`Visuals`] have color on.
+pub fn colored(text: &str, palette: &Palette) -> String {
+    match current_visuals(|visuals| visuals.color) {
+        true => palette.paint(text),
+        false => text.to_string(),
+    }
+}
+
+/// Like [`visualize_colored`], for a graph rendered through the viewport of the current
+/// [`Visuals`].
+pub fn visualize_graph<T>(day: u8, part: u8, graph: &T, palette: &Palette)
+where
+    T: ChristmasGraph,
+{
+    let (enabled, viewport) =
+        current_visuals(|visuals| (visuals.sink != Sink::Disabled, visuals.viewport));
+
+    if enabled {
+        visualize_colored(day, part, &graph.render(&viewport), palette);
+    }
+}
+
+/// Saves the graph as `dayNptM.svg` in the SVG directory of the current [`Visuals`], cropped
+/// and scaled by their viewport.
+pub fn visualize_svg<T>(day: u8, part: u8, graph: &T, style: &SvgStyle)
+where
+    T: ChristmasGraph,
+{
+    let (dir, viewport) = current_visuals(|visuals| (visuals.svg_dir.clone(), visuals.viewport));
+
+    let Some(dir) = dir else {
+        return;
+    };
+
+    let path = dir.join(format!("day{}pt{}.svg", day, part));
+
+    if let Err(err) = graph.save_svg(&path, style, &viewport) {
+        eprintln!("warning: could not save visualization, {}", err);
+    }
+}
+
+pub fn visualize<T>(day: u8, part: u8, value: &T)
+where
+    T: Display,
+{
+    visualize_colored(day, part, value, &Palette(&[]));
+}
+
+/// Like [`visualize`], colored with the palette when printed to a terminal.
+pub fn visualize_colored<T>(day: u8, part: u8, value: &T, palette: &Palette)
+where
+    T: Display,
+{
+    let sink = current_visuals(|visuals| visuals.sink.clone());
+
+    match sink {
+        Sink::Disabled => {}
+        Sink::Stderr => eprintln!("{}", colored(&value.to_string(), palette)),
+        Sink::Files(dir) => {
+            let count = CONTEXT.with(|context| {
+                let mut context = context.borrow_mut();
+                let count = context.counts.entry((day, part)).or_default();
+                *count += 1;
+                *count
+            });
+
+            let path = dir.join(format!("day{}pt{}-{}.txt", day, part, count));
+
+            let written = fs::create_dir_all(&dir)
+                .and_then(|()| fs::write(&path, value.to_string()))
+                .map_err(|err| format!("{}: {}", path.display(), err));
+
+            if let Err(err) = written {
+                eprintln!("warning: could not save visualization, {}", err);
             }
         }
+        Sink::Buffer(frames) => frames.push(day, part, value.to_string()),
     }
 }
+
+/// A diagonal line from the top left corner of the graph, `<` first and `#` after.
+#[cfg(test)]
+struct Diagonal(GraphMetadata);
+
+#[cfg(test)]
+impl ChristmasGraph for Diagonal {
+    fn as_graph_metadata(&self) -> GraphMetadata {
+        GraphMetadata { ..self.0 }
+    }
+
+    fn graph_legend_x(&self, value: i64) -> Vec<char> {
+        value.to_string().chars().collect()
+    }
+
+    fn graph_legend_y(&self, value: i64) -> Vec<char> {
+        value.to_string().chars().collect()
+    }
+
+    fn graph_value(&self, x: i64, y: i64) -> Option<char> {
+        let (column, row) = (x - self.0.x, y - self.0.y);
+
+        (column == row).then_some(if row == 0 { '<' } else { '#' })
+    }
+}
+
+#[test]
+fn renders_graph_as_svg() {
+    let graph = Diagonal(GraphMetadata {
+        x: 498,
+        y: 0,
+        width: 3,
+        height: 3,
+        legend_step_x: 2,
+        legend_step_y: 1,
+        legend_y_width: 1,
+        legend_x_width: 3,
+    });
+    let style = SvgStyle::default().color('#', "gray");
+
+    let svg = graph.svg(&style, &Viewport::default());
+
+    assert!(svg.starts_with("<svg "));
+    assert_eq!(svg.matches(r#"fill="gray""#).count(), 3);
+    assert_eq!(svg.matches(r##"fill="#444""##).count(), 2);
+    assert!(svg.contains(">498</text>"));
+    assert!(svg.contains(">500</text>"));
+    assert!(!svg.contains(">499</text>"));
+    assert!(svg.contains(">2</text>"));
+    assert!(svg.contains(">&lt;</text>"));
+    assert!(svg.ends_with("</svg>\n"));
+
+    let cropped = graph.svg(
+        &style,
+        &Viewport {
+            crop: Some(Region::try_from("499,1,2x2").unwrap()),
+            ..Viewport::default()
+        },
+    );
+    assert_eq!(cropped.matches(r#"fill="gray""#).count(), 3);
+    assert!(!cropped.contains(">&lt;</text>"));
+    assert!(!cropped.contains(">498</text>"));
+
+    let scaled = graph.svg(
+        &style,
+        &Viewport {
+            scale: 2,
+            ..Viewport::default()
+        },
+    );
+    assert_eq!(scaled.matches("<rect x=").count(), 2 + 2);
+}
+
+#[test]
+fn paints_palette_colors() {
+    let palette = Palette(&[('#', "90"), ('o', "33"), ('~', "33")]);
+
+    assert_eq!(
+        palette.paint("#.o~\n#"),
+        "\x1b[90m#\x1b[0m.\x1b[33mo~\x1b[0m\n\x1b[90m#\x1b[0m"
+    );
+    assert_eq!(palette.paint("..."), "...");
+    assert_eq!(ColorMode::try_from("always"), Ok(ColorMode::Always));
+    assert!(ColorMode::try_from("yes").is_err());
+}
+
+#[test]
+fn renders_viewports() {
+    let grid = Diagonal(GraphMetadata {
+        x: 0,
+        y: 0,
+        width: 8,
+        height: 6,
+        legend_step_x: 3,
+        legend_step_y: 3,
+        legend_y_width: 1,
+        legend_x_width: 1,
+    });
+
+    let cropped = grid.render(&Viewport {
+        crop: Some(Region::try_from("2,2,3x2").unwrap()),
+        ..Viewport::default()
+    });
+    assert_eq!(cropped, "    3  \n  # . .\n3 . # .\n");
+
+    let scaled = grid.render(&Viewport {
+        scale: 2,
+        ..Viewport::default()
+    });
+    assert_eq!(scaled, "  0 3   6  \n0 < . . .\n3 . # . .\n  . . # .\n");
+
+    let tiles = grid.render(&Viewport {
+        tile: Some((5, 6)),
+        ..Viewport::default()
+    });
+    assert!(tiles.starts_with("x 0..5, y 0..6\n"));
+    assert!(tiles.contains("x 5..8, y 0..6\n    6  \n"));
+
+    assert_eq!(
+        Region::try_from("-1,2,3x4").unwrap().to_string(),
+        "x -1..2, y 2..6"
+    );
+    assert!(Region::try_from("1,2").is_err());
+}
+
+#[test]
+fn buffers_visualizations() {
+    let frames = Frames::default();
+
+    let visuals = Visuals {
+        sink: Sink::Buffer(frames.clone()),
+        ..Visuals::default()
+    };
+
+    with_visuals(visuals, || {
+        visualize(0, 1, &"first");
+        visualize(0, 1, &"second");
+    });
+    visualize(0, 1, &"dropped");
+
+    assert_eq!(frames.get(0, 1), ["first", "second"]);
+    assert!(frames.get(0, 2).is_empty());
+    assert_ne!(Sink::Buffer(frames), Sink::Buffer(Frames::default()));
+}
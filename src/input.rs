@@ -0,0 +1,104 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::answers::ANSWERS_FILE;
+
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    Directory(PathBuf),
+    File(PathBuf),
+    Stdin,
+    #[cfg(feature = "embed-inputs")]
+    Embedded,
+}
+
+impl Default for InputSource {
+    #[cfg(feature = "embed-inputs")]
+    fn default() -> Self {
+        InputSource::Embedded
+    }
+
+    #[cfg(not(feature = "embed-inputs"))]
+    fn default() -> Self {
+        InputSource::Directory(PathBuf::from("input"))
+    }
+}
+
+impl InputSource {
+    pub fn answers_path(&self) -> Option<PathBuf> {
+        match self {
+            InputSource::Directory(dir) => Some(dir.join(ANSWERS_FILE)),
+            InputSource::File(_) | InputSource::Stdin => None,
+            #[cfg(feature = "embed-inputs")]
+            InputSource::Embedded => Some(PathBuf::from("input").join(ANSWERS_FILE)),
+        }
+    }
+}
+
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}.txt", day))
+}
+
+pub struct Inputs {
+    pub source: InputSource,
+    stdin: OnceLock<Result<String, String>>,
+}
+
+impl From<InputSource> for Inputs {
+    fn from(source: InputSource) -> Self {
+        Self {
+            source,
+            stdin: OnceLock::new(),
+        }
+    }
+}
+
+impl Inputs {
+    pub fn load(&self, day: u8) -> Result<String, String> {
+        match &self.source {
+            InputSource::Directory(dir) => read_file(&input_path(dir, day)),
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => self
+                .stdin
+                .get_or_init(|| io::read_to_string(io::stdin()).map_err(|err| err.to_string()))
+                .clone(),
+            #[cfg(feature = "embed-inputs")]
+            InputSource::Embedded => embedded(day)
+                .map(str::to_string)
+                .ok_or_else(|| format!("no embedded input for day {}", day)),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(feature = "embed-inputs")]
+fn embedded(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("../input/day1.txt")),
+        2 => Some(include_str!("../input/day2.txt")),
+        3 => Some(include_str!("../input/day3.txt")),
+        4 => Some(include_str!("../input/day4.txt")),
+        5 => Some(include_str!("../input/day5.txt")),
+        6 => Some(include_str!("../input/day6.txt")),
+        7 => Some(include_str!("../input/day7.txt")),
+        8 => Some(include_str!("../input/day8.txt")),
+        9 => Some(include_str!("../input/day9.txt")),
+        10 => Some(include_str!("../input/day10.txt")),
+        11 => Some(include_str!("../input/day11.txt")),
+        12 => Some(include_str!("../input/day12.txt")),
+        13 => Some(include_str!("../input/day13.txt")),
+        14 => Some(include_str!("../input/day14.txt")),
+        15 => Some(include_str!("../input/day15.txt")),
+        16 => Some(include_str!("../input/day16.txt")),
+        17 => Some(include_str!("../input/day17.txt")),
+        18 => Some(include_str!("../input/day18.txt")),
+        19 => Some(include_str!("../input/day19.txt")),
+        _ => None,
+    }
+}
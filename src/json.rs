@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// As written in the document, so integers too large for an `f64` read back exactly.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&str> for Value {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut chars = value.chars().peekable();
+        let parsed = parse_value(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+
+        match chars.next() {
+            None => Ok(parsed),
+            Some(char) => Err(format!("unexpected '{}' after value", char)),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|char| char.is_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), String> {
+    match chars.next() {
+        Some(char) if char == expected => Ok(()),
+        Some(char) => Err(format!("expected '{}', found '{}'", expected, char)),
+        None => Err(format!("expected '{}', found end of input", expected)),
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Value, String> {
+    skip_whitespace(chars);
+
+    match chars.peek() {
+        Some('{') => parse_object(chars),
+        Some('[') => parse_array(chars),
+        Some('"') => parse_string(chars).map(Value::String),
+        Some('t') => parse_literal(chars, "true", Value::Bool(true)),
+        Some('f') => parse_literal(chars, "false", Value::Bool(false)),
+        Some('n') => parse_literal(chars, "null", Value::Null),
+        Some(char) if *char == '-' || char.is_ascii_digit() => parse_number(chars),
+        Some(char) => Err(format!("unexpected '{}'", char)),
+        None => Err("unexpected end of input".to_string()),
+    }
+}
+
+fn parse_literal(
+    chars: &mut Peekable<Chars>,
+    literal: &str,
+    value: Value,
+) -> Result<Value, String> {
+    for expected in literal.chars() {
+        expect(chars, expected)?;
+    }
+
+    Ok(value)
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Result<Value, String> {
+    let mut number = String::new();
+
+    while let Some(char) = chars.next_if(|char| "+-.eE".contains(*char) || char.is_ascii_digit()) {
+        number.push(char);
+    }
+
+    match number.parse::<f64>() {
+        Ok(_) => Ok(Value::Number(number)),
+        Err(_) => Err(format!("invalid number '{}'", number)),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    expect(chars, '"')?;
+
+    let mut string = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(string),
+            Some('\\') => match chars.next() {
+                Some('n') => string.push('\n'),
+                Some('r') => string.push('\r'),
+                Some('t') => string.push('\t'),
+                Some('b') => string.push('\u{8}'),
+                Some('f') => string.push('\u{c}'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    let char = u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("invalid escape '\\u{}'", code))?;
+                    string.push(char);
+                }
+                Some(char) => string.push(char),
+                None => return Err("unterminated string".to_string()),
+            },
+            Some(char) => string.push(char),
+            None => return Err("unterminated string".to_string()),
+        }
+    }
+}
+
+fn parse_array(chars: &mut Peekable<Chars>) -> Result<Value, String> {
+    expect(chars, '[')?;
+
+    let mut values = vec![];
+
+    skip_whitespace(chars);
+
+    if chars.next_if_eq(&']').is_some() {
+        return Ok(Value::Array(values));
+    }
+
+    loop {
+        values.push(parse_value(chars)?);
+
+        skip_whitespace(chars);
+
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(Value::Array(values)),
+            _ => return Err("expected ',' or ']' in array".to_string()),
+        }
+    }
+}
+
+fn parse_object(chars: &mut Peekable<Chars>) -> Result<Value, String> {
+    expect(chars, '{')?;
+
+    let mut fields = BTreeMap::new();
+
+    skip_whitespace(chars);
+
+    if chars.next_if_eq(&'}').is_some() {
+        return Ok(Value::Object(fields));
+    }
+
+    loop {
+        skip_whitespace(chars);
+        let key = parse_string(chars)?;
+
+        skip_whitespace(chars);
+        expect(chars, ':')?;
+
+        fields.insert(key, parse_value(chars)?);
+
+        skip_whitespace(chars);
+
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(Value::Object(fields)),
+            _ => return Err("expected ',' or '}' in object".to_string()),
+        }
+    }
+}
+
+#[test]
+fn parses_documents() {
+    let value =
+        Value::try_from(r#"{"name": "a\"b", "items": [1, 2.5, -3e2, true, null], "empty": {}}"#)
+            .unwrap();
+
+    assert_eq!(value.get("name").and_then(Value::as_str), Some("a\"b"));
+
+    let items = value.get("items").and_then(Value::as_array).unwrap();
+    assert_eq!(items[0].as_u64(), Some(1));
+    assert_eq!(items[1].as_u64(), None);
+    assert_eq!(items[2], Value::Number("-3e2".to_string()));
+    assert_eq!(items[3], Value::Bool(true));
+    assert_eq!(items[4], Value::Null);
+
+    let large = Value::try_from("[9007199254740993, -9007199254740993]").unwrap();
+    let large = large.as_array().unwrap();
+    assert_eq!(large[0].as_u64(), Some(9_007_199_254_740_993));
+    assert_eq!(large[1].as_u64(), None);
+    // Answers in reports are read back as the text of the number.
+    assert_eq!(large[1], Value::Number("-9007199254740993".to_string()));
+
+    assert!(Value::try_from("[1, 2").is_err());
+    assert!(Value::try_from("{} x").is_err());
+}
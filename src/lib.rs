@@ -0,0 +1,89 @@
+//! Advent of Code 2022 solutions and the tooling to run, check and benchmark them.
+//!
+//! Every day has a module with its model types and `solve_1`/`solve_2` functions. To solve a
+//! puzzle by number, use [`solve`]:
+//!
+//! ```
+//! use aoc2022::answer::Answer;
+//!
+//! let input = "1000\n2000\n\n4000\n";
+//!
+//! assert_eq!(aoc2022::solve(1, 1, input), Ok(Answer::Integer(4000)));
+//! assert!(aoc2022::solve(1, 3, input).is_err());
+//! assert!(aoc2022::solve(1, 1, "not a number").is_err());
+//! ```
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub mod answer;
+pub mod extra;
+pub mod memory;
+pub mod registry;
+pub mod solution;
+
+// Tooling behind the binary, public for it but not meant to be used as a library.
+#[doc(hidden)]
+pub mod answers;
+#[doc(hidden)]
+pub mod baseline;
+#[doc(hidden)]
+pub mod bench;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod examples;
+#[doc(hidden)]
+pub mod input;
+#[doc(hidden)]
+pub mod json;
+#[doc(hidden)]
+pub mod record;
+#[doc(hidden)]
+pub mod report;
+#[doc(hidden)]
+pub mod runner;
+#[doc(hidden)]
+pub mod scaffold;
+#[doc(hidden)]
+pub mod tui;
+#[doc(hidden)]
+pub mod watch;
+
+/// Parses `input` and solves the part of the day's puzzle.
+///
+/// The solutions assume well-formed puzzle input and panic on anything else. Such panics are
+/// caught and returned as errors, but are still printed by the default panic hook.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<answer::Answer, String> {
+    let solution = registry::find(day).ok_or_else(|| format!("day {} has no solution", day))?;
+
+    if !(1..=2).contains(&part) {
+        return Err(format!("invalid part {}, expected 1 or 2", part));
+    }
+
+    runner::catch_panic(|| solution.solve(part, &solution.parse(input))).map_err(|status| {
+        match status {
+            runner::Status::Panicked(message) => message,
+            _ => format!("day {} part {} is not solved yet", day, part),
+        }
+    })
+}
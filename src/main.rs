@@ -1,400 +1,278 @@
-use std::{
-    env,
-    time::{Duration, Instant},
+use std::{env, fs, path::Path, process, sync::Arc};
+
+use aoc2022::{
+    answers::Answers,
+    baseline::{self, Baseline},
+    bench,
+    cache::{Cache, CACHE_FILE},
+    cli::{self, Command, Options},
+    extra::{Sink, Visuals},
+    input::Inputs,
+    registry,
+    report::{self, Format},
+    runner::{self, Job, Run, Settings, Status},
+    scaffold, tui, watch,
 };
 
-use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-mod extra;
-
-const PRINT_WIDTH: usize = 80;
-const TIME_PAD_WIDTH: usize = 15;
-
 fn main() {
-    let enumerated_inputs: Vec<(usize, &str)> = vec![
-        include_str!("../input/day1.txt"),
-        include_str!("../input/day1.txt"),
-        include_str!("../input/day2.txt"),
-        include_str!("../input/day2.txt"),
-        include_str!("../input/day3.txt"),
-        include_str!("../input/day3.txt"),
-        include_str!("../input/day4.txt"),
-        include_str!("../input/day4.txt"),
-        include_str!("../input/day5.txt"),
-        include_str!("../input/day5.txt"),
-        include_str!("../input/day6.txt"),
-        include_str!("../input/day6.txt"),
-        include_str!("../input/day7.txt"),
-        include_str!("../input/day7.txt"),
-        include_str!("../input/day8.txt"),
-        include_str!("../input/day8.txt"),
-        include_str!("../input/day9.txt"),
-        include_str!("../input/day9.txt"),
-        include_str!("../input/day10.txt"),
-        include_str!("../input/day10.txt"),
-        include_str!("../input/day11.txt"),
-        include_str!("../input/day11.txt"),
-        include_str!("../input/day12.txt"),
-        include_str!("../input/day12.txt"),
-        include_str!("../input/day13.txt"),
-        include_str!("../input/day13.txt"),
-        include_str!("../input/day14.txt"),
-        include_str!("../input/day14.txt"),
-        include_str!("../input/day15.txt"),
-        include_str!("../input/day15.txt"),
-        include_str!("../input/day16.txt"),
-        include_str!("../input/day16.txt"),
-        include_str!("../input/day17.txt"),
-        include_str!("../input/day17.txt"),
-        include_str!("../input/day18.txt"),
-        include_str!("../input/day18.txt"),
-        include_str!("../input/day19.txt"),
-        include_str!("../input/day19.txt"),
-    ]
-    .into_iter()
-    .enumerate()
-    .collect();
-
-    let start = Instant::now();
-
-    let mut results: Vec<(usize, Duration, String)> = enumerated_inputs
-        .par_iter()
-        .map(|(index, input)| {
-            let day = (index) / 2 + 1;
-            let part = (index) % 2 + 1;
-
-            if let Ok(day_str) = env::var("DAY") {
-                if day_str.parse::<usize>().unwrap() != day {
-                    return (*index, Duration::ZERO, "SKIPPED".to_string());
-                }
-
-                if let Ok(part_str) = env::var("PART") {
-                    if part_str.parse::<usize>().unwrap() != part {
-                        return (*index, Duration::ZERO, "SKIPPED".to_string());
-                    }
-                }
-            }
+    runner::install_panic_hook();
+
+    let options = match cli::parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            eprintln!();
+            eprintln!("{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
 
-            let start = Instant::now();
-
-            let result = match (day, part) {
-                (1, 1) => day1::solve_1(input),
-                (1, 2) => day1::solve_2(input),
-                (2, 1) => day2::solve_1(input),
-                (2, 2) => day2::solve_2(input),
-                (3, 1) => day3::solve_1(input),
-                (3, 2) => day3::solve_2(input),
-                (4, 1) => day4::solve_1(input),
-                (4, 2) => day4::solve_2(input),
-                (5, 1) => day5::solve_1(input),
-                (5, 2) => day5::solve_2(input),
-                (6, 1) => day6::solve_1(input),
-                (6, 2) => day6::solve_2(input),
-                (7, 1) => day7::solve_1(input),
-                (7, 2) => day7::solve_2(input),
-                (8, 1) => day8::solve_1(input),
-                (8, 2) => day8::solve_2(input),
-                (9, 1) => day9::solve_1(input),
-                (9, 2) => day9::solve_2(input),
-                (10, 1) => day10::solve_1(input),
-                (10, 2) => day10::solve_2(input),
-                (11, 1) => day11::solve_1(input),
-                (11, 2) => day11::solve_2(input),
-                (12, 1) => day12::solve_1(input),
-                (12, 2) => day12::solve_2(input),
-                (13, 1) => day13::solve_1(input),
-                (13, 2) => day13::solve_2(input),
-                (14, 1) => day14::solve_1(input),
-                (14, 2) => day14::solve_2(input),
-                (15, 1) => day15::solve_1(input),
-                (15, 2) => day15::solve_2(input),
-                (16, 1) => day16::solve_1(input),
-                (16, 2) => day16::solve_2(input),
-                (17, 1) => day17::solve_1(input),
-                (17, 2) => day17::solve_2(input),
-                (18, 1) => day18::solve_1(input),
-                (18, 2) => day18::solve_2(input),
-                (19, 1) => day19::solve_1(input),
-                (19, 2) => day19::solve_2(input),
-                _ => unreachable!(),
-            };
-
-            let duration = start.elapsed();
-
-            (*index, duration, result)
-        })
-        .collect();
+    match options.command {
+        Command::Run | Command::Verify => run(&options),
+        Command::List => list(),
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Bench => bench(&options),
+        Command::New => new_day(options.days[0]),
+        Command::Watch => watch::watch(options.days[0], &options),
+    }
+}
 
-    let duration = start.elapsed();
+fn list() {
+    for solution in registry::SOLUTIONS {
+        println!("{:<7}{}", format!("day{}", solution.day()), solution.name());
+    }
+}
 
-    results.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+fn new_day(day: u8) {
+    match scaffold::new_day(Path::new("."), day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("{}", path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    }
+}
 
-    let mut sum_duration = Duration::ZERO;
+fn jobs(options: &Options) -> Vec<Job> {
+    options
+        .days
+        .iter()
+        .filter_map(|day| registry::find(*day))
+        .flat_map(|solution| {
+            options.parts.iter().map(move |part| Job {
+                solution,
+                part: *part,
+            })
+        })
+        .collect()
+}
 
-    for (index, duration, result) in results {
-        sum_duration += duration;
+fn run(options: &Options) {
+    let inputs = Arc::new(Inputs::from(options.input.clone()));
+    let baseline = load_baseline(options);
 
-        let day = (index) / 2 + 1;
-        let part = (index % 2) + 1;
-        let multiline = day == 10 && part == 2;
+    let jobs = jobs(options);
 
-        let report = display_with_duration(
-            80,
-            15,
-            multiline,
-            format!("day{}pt{}: ", day, part).as_str(),
-            "-",
-            result.as_str(),
-            duration,
-        );
+    let cache_path = Path::new(CACHE_FILE);
 
-        println!("{}", report);
+    if options.clear_cache {
+        if let Err(err) = Cache::clear(cache_path) {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
     }
 
-    println!();
-
-    println!(
-        "{}",
-        display_with_duration(
-            PRINT_WIDTH,
-            TIME_PAD_WIDTH,
-            false,
-            "> ",
-            "-",
-            "total thread time",
-            sum_duration,
-        )
-    );
-
-    println!(
-        "{}",
-        display_with_duration(
-            PRINT_WIDTH,
-            TIME_PAD_WIDTH,
-            false,
-            "> ",
-            "-",
-            "real time",
-            duration,
-        )
-    );
-}
-
-fn display_with_duration(
-    target_len: usize,
-    time_len: usize,
-    multiline: bool,
-    prefix: &str,
-    suffix: &str,
-    content: &str,
-    duration: Duration,
-) -> String {
-    let mut output = String::new();
-    output += prefix;
-    if multiline {
-        output += "\n";
-    }
-    let duration_str = format!("{:?}", duration);
-    let content_len = content.lines().map(|line| line.len()).max().unwrap();
-    let pad_len =
-        target_len - if !multiline { prefix.len() } else { 0 } - content_len - suffix.len();
-
-    let main_pad = " ".repeat(pad_len - time_len);
-    let time_pad = " ".repeat(time_len - duration_str.len());
-    output += format!(
-        "{}{}{}{}{}",
-        content, main_pad, suffix, time_pad, duration_str
-    )
-    .as_str();
-
-    output
-}
+    // The cache only knows the input, not the parameters.
+    let update_cache = options.cache && options.params.is_empty();
+
+    // Cached answers have no timings worth comparing or saving, and draw nothing.
+    let use_cache = update_cache
+        && options.baseline.is_none()
+        && options.save_baseline.is_none()
+        && options.svg.is_none()
+        && options.record.is_none()
+        && options.sink == Sink::Disabled;
+
+    let mut cache = Cache::load(cache_path).unwrap_or_else(|err| {
+        eprintln!("warning: ignoring the answer cache, {}", err);
+        Cache::default()
+    });
+
+    let settings = Settings {
+        timeout: options.timeout,
+        schedule: options.schedule,
+        threads: options.threads,
+        estimates: baseline
+            .as_ref()
+            .map(|baseline| baseline.timings.clone())
+            .unwrap_or_default(),
+        cache: use_cache.then(|| cache.clone()),
+        params: options.params.clone(),
+        visuals: Visuals {
+            sink: options.sink.clone(),
+            color: options.color.enabled(),
+            viewport: options.viewport,
+            svg_dir: options.svg.clone(),
+            record_dir: options.record.clone(),
+        },
+        progress: None,
+    };
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        day1, day10, day11, day12, day13, day14, day15, day16, day17, day2, day3, day4, day5, day6,
-        day7, day8, day8::TreeGrid, day9,
+    let answers_path = options
+        .answers
+        .clone()
+        .or_else(|| options.input.answers_path());
+
+    let mut answers = match &answers_path {
+        Some(path) => Answers::load(path).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }),
+        None => Answers::default(),
     };
 
-    #[test]
-    fn day1_eq_example() {
-        let input = include_str!("../example_input/day1.txt");
-        assert_eq!(day1::solve_1(input), "24000");
-        assert_eq!(day1::solve_2(input), "45000");
-    }
+    let mut run = match options.tui {
+        true => match tui::dashboard(&jobs, &inputs, &settings, &answers) {
+            Ok(Some(run)) => run,
+            // Closed before the solutions were done, they can't be stopped in any other way.
+            Ok(None) => process::exit(130),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(2);
+            }
+        },
+        false => runner::run(&jobs, &inputs, &settings),
+    };
 
-    #[test]
-    fn day2_eq_example() {
-        let input = include_str!("../example_input/day2.txt");
-        assert_eq!(day2::solve_1(input), "15");
-        assert_eq!(day2::solve_2(input), "12");
+    for result in &mut run.results {
+        answers.verify(result);
     }
 
-    #[test]
-    fn day3_eq_example() {
-        let input = include_str!("../example_input/day3.txt");
-        assert_eq!(day3::solve_1(input), "157");
-        assert_eq!(day3::solve_2(input), "70");
+    match (options.command, options.format) {
+        (Command::Verify, Format::Text) => print!("{}", report::verification(&run)),
+        _ => print!("{}", report::render(options.format, &run, &options.title)),
     }
 
-    #[test]
-    fn day4_eq_example() {
-        let input = include_str!("../example_input/day4.txt");
-        assert_eq!(day4::solve_1(input), "2");
-        assert_eq!(day4::solve_2(input), "4");
+    for (format, path) in &options.outputs {
+        if let Err(err) = fs::write(path, report::render(*format, &run, &options.title)) {
+            eprintln!("error: {}: {}", path.display(), err);
+            process::exit(2);
+        }
     }
 
-    #[test]
-    fn day5_eq_example() {
-        let input = include_str!("../example_input/day5.txt");
-        assert_eq!(day5::solve_1(input), "CMZ".to_string());
-        assert_eq!(day5::solve_2(input), "MCD".to_string());
-    }
+    if update_cache {
+        cache.update(&run, &inputs);
 
-    #[test]
-    fn day6_eq_example() {
-        let input = include_str!("../example_input/day6.txt");
-        assert_eq!(day6::solve_1(input), "7");
-        assert_eq!(day6::solve_2(input), "19");
+        if let Err(err) = cache.save(cache_path) {
+            eprintln!("warning: could not update the answer cache, {}", err);
+        }
     }
 
-    #[test]
-    fn day7_eq_example() {
-        let input = include_str!("../example_input/day7.txt");
-        assert_eq!(day7::solve_1(input), "95437");
-        assert_eq!(day7::solve_2(input), "24933642");
+    if options.save_answers {
+        save_answers(&mut answers, answers_path.as_deref(), &run);
     }
 
-    #[test]
-    fn day8_eq_example() {
-        let input = include_str!("../example_input/day8.txt");
-        assert_eq!(day8::solve_1(input), "21");
-        let scan = TreeGrid::from(input);
-        assert_eq!(scan.scenic_score(&day8::Coord { x: 2, y: 1 }), 4);
-        assert_eq!(scan.scenic_score(&day8::Coord { x: 0, y: 0 }), 0);
-        assert_eq!(day8::solve_2(input), "8")
-    }
+    let regressions = compare_baseline(
+        options,
+        baseline,
+        Baseline::from_run(current_baseline_name(options), &run),
+    );
 
-    #[test]
-    fn day9_eq_example() {
-        let input = include_str!("../example_input/day9.txt");
-        assert_eq!(day9::solve_1(input), "13");
-        assert_eq!(day9::solve_2(input), "1");
-        let input = include_str!("../example_input/day9pt2.txt");
-        assert_eq!(day9::solve_2(input), "36");
+    if run.failures() > 0 || run.crashes() > 0 || regressions {
+        process::exit(1);
     }
+}
 
-    #[test]
-    fn day10_eq_example() {
-        let input = include_str!("../example_input/day10.txt");
-
-        assert_eq!(day10::solve_1(input), "13140");
-
-        assert_eq!(
-            day10::solve_2(input),
-            r"##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######....."
-        );
-    }
+fn bench(options: &Options) {
+    let inputs = Inputs::from(options.input.clone());
+    let baseline = load_baseline(options);
+    let jobs = jobs(options);
 
-    #[test]
-    fn day11_eq_example() {
-        let input = include_str!("../example_input/day11.txt");
+    let results = bench::bench(&jobs, &inputs, options.warmup, options.runs);
 
-        assert_eq!(day11::solve_1(input), "10605");
-        assert_eq!(day11::solve_2(input), "2713310158");
-    }
+    print!("{}", bench::render(&results));
 
-    #[test]
-    fn day12_eq_example() {
-        let input = include_str!("../example_input/day12.txt");
+    let regressions = compare_baseline(
+        options,
+        baseline,
+        Baseline::from_bench(current_baseline_name(options), &results),
+    );
 
-        assert_eq!(day12::solve_1(input), "31");
-        assert_eq!(day12::solve_2(input), "29");
+    if regressions {
+        process::exit(1);
     }
+}
 
-    #[test]
-    fn day13_eq_example() {
-        let input = include_str!("../example_input/day13.txt");
+fn load_baseline(options: &Options) -> Option<Baseline> {
+    let name = options.baseline.as_deref()?;
 
-        assert_eq!(day13::solve_1(input), "13");
-        assert_eq!(day13::solve_2(input), "140");
+    match Baseline::load(name) {
+        Ok(baseline) => Some(baseline),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
     }
+}
 
-    #[test]
-    fn day14_eq_example() {
-        let input = include_str!("../example_input/day14.txt");
+fn current_baseline_name(options: &Options) -> &str {
+    options.save_baseline.as_deref().unwrap_or("current")
+}
 
-        assert_eq!(day14::solve_1(input), "24");
-        assert_eq!(day14::solve_2(input), "93");
+/// Saves and compares against baselines as requested, returns whether a regression should
+/// fail the run.
+fn compare_baseline(options: &Options, baseline: Option<Baseline>, current: Baseline) -> bool {
+    if options.save_baseline.is_some() {
+        match current.save() {
+            Ok(path) => eprintln!("saved baseline to {}", path.display()),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(2);
+            }
+        }
     }
 
-    #[test]
-    fn day15_eq_example() {
-        let input = include_str!("../example_input/day15.txt");
-
-        assert_eq!(day15::solve_1(input), "26");
-        assert_eq!(day15::solve_2(input), "56000011");
-    }
+    let Some(baseline) = baseline else {
+        return false;
+    };
 
-    #[test]
-    fn day16_eq_example() {
-        let input = include_str!("../example_input/day16.txt");
+    let comparisons = baseline.compare(&current, options.threshold);
+    let comparison = baseline::render(&baseline, &comparisons);
 
-        assert_eq!(day16::solve_1(input), "1651");
-        assert_eq!(day16::solve_2(input), "1707");
+    // Keep machine readable output parseable.
+    match options.format {
+        Format::Text => print!("\n{}", comparison),
+        _ => eprint!("{}", comparison),
     }
 
-    #[test]
-    fn day17_eq_example() {
-        let input = include_str!("../example_input/day17.txt");
-
-        assert_eq!(day17::solve_1(input), "3068");
-        assert_eq!(day17::solve_2(input), "3068");
-    }
+    options.fail_on_regression && comparisons.iter().any(|comparison| comparison.regression)
+}
 
-    #[test]
-    fn day18_eq_example() {
-        use crate::day18;
+fn save_answers(answers: &mut Answers, path: Option<&Path>, run: &Run) {
+    let Some(path) = path else {
+        eprintln!("error: no answers file for this input, use --answers <FILE>");
+        process::exit(2);
+    };
 
-        let input = include_str!("../example_input/day18.txt");
+    let mut saved = 0;
 
-        assert_eq!(day18::solve_1(input), "64");
-        assert_eq!(day18::solve_2(input), "58");
+    for result in &run.results {
+        if let (Status::Unknown, Some(answer)) = (&result.status, &result.answer) {
+            answers
+                .entries
+                .insert((result.day, result.part), answer.clone());
+            saved += 1;
+        }
     }
 
-    #[test]
-    fn day19_eq_example() {
-        use crate::day19;
-
-        let input = include_str!("../example_input/day19.txt");
-
-        assert_eq!(day19::solve_1(input), "33");
-        assert_eq!(day19::solve_2(input), "58");
+    if let Err(err) = answers.save(path) {
+        eprintln!("error: {}", err);
+        process::exit(2);
     }
+
+    eprintln!("saved {} new answers to {}", saved, path.display());
 }
@@ -0,0 +1,165 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Whether allocations are counted, see the `count-allocations` feature.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+static ABANDONED: AtomicBool = AtomicBool::new(false);
+
+/// Stops measuring for good, because a solution that ran out of time is still running and
+/// allocating in the background, and would end up in every later measurement.
+pub fn abandon() {
+    ABANDONED.store(true, Ordering::SeqCst);
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Memory {
+    pub allocations: usize,
+    /// Total bytes allocated, including memory that was freed again.
+    pub allocated: usize,
+    /// Highest number of bytes alive at the same time.
+    pub peak: usize,
+}
+
+/// Byte count printed with a binary unit, e.g. `1.5 MiB`.
+pub struct Bytes(pub usize);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return f.pad(&format!("{} B", self.0));
+        }
+
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        f.pad(&format!("{:.1} {}", value, UNITS[unit]))
+    }
+}
+
+/// Runs `f` and reports what it allocated, `None` unless the counting allocator is installed
+/// and measuring wasn't [abandoned](abandon).
+///
+/// The counters are global, so this is only meaningful when nothing else allocates in the
+/// meantime. The runner solves one part at a time while counting for that reason.
+#[cfg(feature = "count-allocations")]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    use std::sync::atomic::Ordering::SeqCst;
+
+    use counting::{ALLOCATED, ALLOCATIONS, LIVE, PEAK};
+
+    let allocations = ALLOCATIONS.load(SeqCst);
+    let allocated = ALLOCATED.load(SeqCst);
+    let live = LIVE.load(SeqCst);
+    PEAK.store(live, SeqCst);
+
+    let output = f();
+
+    let memory = Memory {
+        allocations: ALLOCATIONS.load(SeqCst) - allocations,
+        allocated: ALLOCATED.load(SeqCst) - allocated,
+        peak: PEAK.load(SeqCst).saturating_sub(live),
+    };
+
+    match ABANDONED.load(SeqCst) {
+        true => (output, None),
+        false => (output, Some(memory)),
+    }
+}
+
+#[cfg(not(feature = "count-allocations"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    (f(), None)
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering::SeqCst},
+    };
+
+    pub static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    pub static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    pub static LIVE: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// The system allocator, counting every allocation on the way.
+    pub struct Counter;
+
+    #[global_allocator]
+    static GLOBAL: Counter = Counter;
+
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, SeqCst);
+        ALLOCATED.fetch_add(size, SeqCst);
+        let live = LIVE.fetch_add(size, SeqCst) + size;
+        PEAK.fetch_max(live, SeqCst);
+    }
+
+    unsafe impl GlobalAlloc for Counter {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            LIVE.fetch_sub(layout.size(), SeqCst);
+        }
+
+        /// Counted as a new allocation of `new_size` bytes replacing the old one.
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                LIVE.fetch_sub(layout.size(), SeqCst);
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+}
+
+#[test]
+fn formats_bytes() {
+    assert_eq!(Bytes(512).to_string(), "512 B");
+    assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+    assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0 MiB");
+    assert_eq!(format!("{:>8}", Bytes(10)), "    10 B");
+}
+
+#[cfg(feature = "count-allocations")]
+#[test]
+fn counts_allocations() {
+    let (_, memory) = measure(|| {
+        let first = vec![0u8; 4096];
+        drop(first);
+        let second = vec![0u8; 1024];
+        second.len()
+    });
+
+    let memory = memory.unwrap();
+
+    assert!(memory.allocations >= 2);
+    assert!(memory.allocated >= 5120);
+}
@@ -0,0 +1,437 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    extra::{self, Palette, SvgStyle},
+    report::json_string,
+};
+
+/// Time between frames in the recordings.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(80);
+
+/// How long the last frame stays up before an animation loops.
+const FINAL_FRAME_TIME: Duration = Duration::from_secs(2);
+
+/// Frames kept per recording. Longer simulations keep every other frame, then every fourth
+/// and so on, so they still play from start to end.
+pub const MAX_FRAMES: usize = 400;
+
+/// Collects the states of a simulation as text frames and saves them as `dayNptM.cast` for
+/// asciinema and `dayNptM.gif`.
+pub struct Recorder {
+    pub day: u8,
+    pub part: u8,
+    dir: Option<PathBuf>,
+    pub frames: Vec<String>,
+    max_frames: usize,
+    /// Only every `stride`th pushed frame is kept.
+    stride: usize,
+    pushed: usize,
+}
+
+impl Recorder {
+    /// A recorder for the part that only keeps frames if the current [`Visuals`](extra::Visuals) have a
+    /// record directory, so pushing is cheap otherwise.
+    pub fn start(day: u8, part: u8) -> Self {
+        Self {
+            day,
+            part,
+            dir: extra::current_visuals(|visuals| visuals.record_dir.clone()),
+            frames: vec![],
+            max_frames: MAX_FRAMES,
+            stride: 1,
+            pushed: 0,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Adds a frame, which is only rendered when it is kept.
+    pub fn push<T>(&mut self, frame: &T)
+    where
+        T: Display,
+    {
+        if !self.is_recording() {
+            return;
+        }
+
+        if self.pushed % self.stride == 0 {
+            self.frames.push(frame.to_string());
+
+            if self.frames.len() > self.max_frames {
+                let mut index = 0;
+                self.frames.retain(|_| {
+                    index += 1;
+                    index % 2 == 1
+                });
+                self.stride *= 2;
+            }
+        }
+
+        self.pushed += 1;
+    }
+
+    /// Adds the final state, whether or not it falls on the stride, and saves the recording.
+    pub fn finish<T>(mut self, last: &T, palette: &Palette, style: &SvgStyle)
+    where
+        T: Display,
+    {
+        let Some(dir) = self.dir.clone() else {
+            return;
+        };
+
+        let last = last.to_string();
+        if self.frames.last() != Some(&last) {
+            self.frames.push(last);
+        }
+
+        let name = format!("day{}pt{}", self.day, self.part);
+
+        let saved = save(&dir.join(format!("{}.cast", name)), self.asciicast(palette))
+            .and_then(|()| save(&dir.join(format!("{}.gif", name)), self.gif(style)));
+
+        if let Err(err) = saved {
+            eprintln!("warning: could not save recording, {}", err);
+        }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let width = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.lines())
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = self
+            .frames
+            .iter()
+            .map(|frame| frame.lines().count())
+            .max()
+            .unwrap_or(0);
+
+        (width, height)
+    }
+
+    /// The frames as an asciinema v2 recording, colored with the palette.
+    pub fn asciicast(&self, palette: &Palette) -> String {
+        let (width, height) = self.size();
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"title\": \"day {} part {}\"}}",
+            width, height, self.day, self.part
+        )
+        .unwrap();
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            let screen = "\x1b[H\x1b[2J".to_string()
+                + &palette.paint(frame).trim_end().replace('\n', "\r\n");
+
+            writeln!(
+                output,
+                "[{:.3}, \"o\", {}]",
+                (FRAME_INTERVAL * index as u32).as_secs_f64(),
+                json_string(&screen)
+            )
+            .unwrap();
+        }
+
+        output
+    }
+
+    /// The frames as a looping GIF with a block of `style.cell_size` pixels per character.
+    /// Only characters with a color in the style are drawn, everything else, like legends
+    /// and empty cells, is background.
+    pub fn gif(&self, style: &SvgStyle) -> Vec<u8> {
+        let (columns, rows) = self.size();
+        let cell = style.cell_size.max(1) as usize;
+        let width = (columns * cell).clamp(1, u16::MAX as usize);
+        let height = (rows * cell).clamp(1, u16::MAX as usize);
+
+        let mut colors = vec![rgb(&style.background).unwrap_or([255, 255, 255])];
+        let mut indices: HashMap<char, u8> = HashMap::new();
+
+        // A GIF has at most 256 colors, one is the background.
+        for (char, color) in style.colors.iter().take(255) {
+            indices.insert(*char, colors.len() as u8);
+            colors.push(rgb(color).or(rgb(&style.default_color)).unwrap_or([68; 3]));
+        }
+
+        let bits = (usize::BITS - (colors.len() - 1).leading_zeros()).max(1);
+        colors.resize(1 << bits, [0; 3]);
+
+        let mut output = b"GIF89a".to_vec();
+        output.extend((width as u16).to_le_bytes());
+        output.extend((height as u16).to_le_bytes());
+        output.extend([0x80 | ((bits as u8 - 1) << 4) | (bits as u8 - 1), 0, 0]);
+        output.extend(colors.concat());
+
+        // Loop forever.
+        output.extend([0x21, 0xff, 0x0b]);
+        output.extend(b"NETSCAPE2.0");
+        output.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            let delay = match index + 1 == self.frames.len() {
+                true => FINAL_FRAME_TIME,
+                false => FRAME_INTERVAL,
+            };
+            let delay = (delay.as_millis() / 10) as u16;
+
+            output.extend([0x21, 0xf9, 0x04, 0x04]);
+            output.extend(delay.to_le_bytes());
+            output.extend([0x00, 0x00]);
+
+            output.push(0x2c);
+            output.extend([0, 0, 0, 0]);
+            output.extend((width as u16).to_le_bytes());
+            output.extend((height as u16).to_le_bytes());
+            output.push(0);
+
+            let lines: Vec<Vec<char>> = frame.lines().map(|line| line.chars().collect()).collect();
+            let mut pixels = Vec::with_capacity(width * height);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let char = lines.get(y / cell).and_then(|line| line.get(x / cell));
+                    pixels.push(
+                        char.and_then(|char| indices.get(char))
+                            .copied()
+                            .unwrap_or(0),
+                    );
+                }
+            }
+
+            let min_code_size = bits.max(2) as u8;
+            output.push(min_code_size);
+
+            for block in lzw(&pixels, min_code_size).chunks(255) {
+                output.push(block.len() as u8);
+                output.extend(block);
+            }
+            output.push(0);
+        }
+
+        output.push(0x3b);
+        output
+    }
+}
+
+fn save(path: &Path, content: impl AsRef<[u8]>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("{}: {}", parent.display(), err))?;
+    }
+
+    fs::write(path, content).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Reads `#rgb` and `#rrggbb` colors, the only ones a GIF can use from an [`SvgStyle`].
+fn rgb(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+
+    match hex.len() {
+        3 => {
+            let mut output = [0; 3];
+            for (index, digit) in hex.chars().enumerate() {
+                output[index] = channel(&digit.to_string())? * 17;
+            }
+            Some(output)
+        }
+        6 => Some([
+            channel(hex.get(0..2)?)?,
+            channel(hex.get(2..4)?)?,
+            channel(hex.get(4..6)?)?,
+        ]),
+        _ => None,
+    }
+}
+
+/// The variable width LZW compression of GIF image data.
+fn lzw(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut output = vec![];
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+
+    let mut write = |code: u16, width: u32| {
+        buffer |= (code as u32) << buffered;
+        buffered += width;
+
+        while buffered >= 8 {
+            output.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    };
+
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size as u32 + 1;
+    let mut prefix: Option<u16> = None;
+
+    write(clear, width);
+
+    for &pixel in pixels {
+        let Some(current) = prefix else {
+            prefix = Some(pixel as u16);
+            continue;
+        };
+
+        if let Some(&code) = codes.get(&(current, pixel)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        write(current, width);
+
+        if next < MAX_CODE {
+            codes.insert((current, pixel), next);
+            next += 1;
+
+            if next > 1 << width && width < 12 {
+                width += 1;
+            }
+        } else {
+            write(clear, width);
+            codes.clear();
+            next = end + 1;
+            width = min_code_size as u32 + 1;
+        }
+
+        prefix = Some(pixel as u16);
+    }
+
+    if let Some(current) = prefix {
+        write(current, width);
+    }
+    write(end, width);
+
+    if buffered > 0 {
+        output.push(buffer as u8);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lzw, rgb, Recorder};
+    use crate::extra::{Palette, SvgStyle};
+
+    /// A plain GIF decoder for the image data, to check the encoder against.
+    fn unlzw(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+
+        let mut table: Vec<Vec<u8>> = vec![];
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|index| vec![index as u8]).collect();
+            table.extend([vec![], vec![]]);
+        };
+        reset(&mut table);
+
+        let mut width = min_code_size as usize + 1;
+        let mut position = 0;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+
+        loop {
+            let code = (0..width)
+                .map(|bit| ((data[(position + bit) / 8] >> ((position + bit) % 8)) & 1) as usize)
+                .enumerate()
+                .fold(0, |code, (bit, value)| code | value << bit);
+            position += width;
+
+            if code == clear {
+                reset(&mut table);
+                width = min_code_size as usize + 1;
+                previous = None;
+                continue;
+            }
+
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("invalid first code {}", code),
+            };
+
+            if let Some(previous) = previous {
+                table.push([previous, vec![entry[0]]].concat());
+
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn compresses_like_gif() {
+        let noise: Vec<u8> = (0..20_000u32)
+            .map(|index| (index.wrapping_mul(2_654_435_761) >> 29) as u8)
+            .collect();
+
+        for pixels in [vec![], vec![1; 5000], noise] {
+            assert_eq!(unlzw(&lzw(&pixels, 3), 3), pixels);
+        }
+    }
+
+    #[test]
+    fn records_frames() {
+        let mut recorder = Recorder::start(14, 1);
+        recorder.dir = Some("unused".into());
+        recorder.max_frames = 4;
+
+        for frame in 0..10 {
+            recorder.push(&format!("#{}", frame));
+        }
+
+        assert_eq!(recorder.frames, ["#0", "#4", "#8"]);
+
+        let cast = recorder.asciicast(&Palette(&[('#', "33")]));
+        let lines: Vec<&str> = cast.lines().collect();
+
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 2, \"height\": 1"));
+        assert_eq!(
+            lines[2],
+            r#"[0.080, "o", "\u001b[H\u001b[2J\u001b[33m#\u001b[0m4"]"#
+        );
+
+        let gif = recorder.gif(&SvgStyle::default().color('#', "#f00"));
+
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert_eq!(
+            gif.windows(11)
+                .filter(|window| window == b"NETSCAPE2.0")
+                .count(),
+            1
+        );
+
+        assert_eq!(rgb("#f80"), Some([255, 136, 0]));
+        assert_eq!(rgb("#6e6259"), Some([110, 98, 89]));
+        assert_eq!(rgb("gray"), None);
+    }
+}
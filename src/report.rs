@@ -0,0 +1,935 @@
+use std::{fmt::Write, path::Path, time::Duration};
+
+use crate::{
+    answer::Answer,
+    baseline::{timing_name, PARSE_PART},
+    memory::{Bytes, Memory},
+    runner::{Run, SolveResult, Status},
+};
+
+pub const PRINT_WIDTH: usize = 80;
+pub const TIME_PAD_WIDTH: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Junit,
+    Markdown,
+    Html,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "junit" => Ok(Format::Junit),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json, csv, junit, markdown or html",
+                value
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// The format of a report file by its extension, e.g. `release.md`.
+    pub fn for_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("txt") => Ok(Format::Text),
+            Some("json") => Ok(Format::Json),
+            Some("csv") => Ok(Format::Csv),
+            Some("xml") => Ok(Format::Junit),
+            Some("md") => Ok(Format::Markdown),
+            Some("html") => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown report format for '{}', expected .txt, .json, .csv, .xml, .md or .html",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Renders the report, `title` heads the markdown and HTML ones.
+pub fn render(format: Format, run: &Run, title: &str) -> String {
+    match format {
+        Format::Text => text(run),
+        Format::Json => json(run),
+        Format::Csv => csv(run),
+        Format::Junit => junit(run),
+        Format::Markdown => markdown(run, title),
+        Format::Html => html(run, title),
+    }
+}
+
+/// What a row of the text report shows for the result, next to its status.
+pub fn row_content(result: &SolveResult) -> String {
+    match &result.status {
+        Status::Fail(expected) if !expected.is_multiline() => {
+            format!("{} (expected {})", result.answer_text(), expected)
+        }
+        Status::Fail(expected) => format!("{}\nexpected:\n{}", result.answer_text(), expected),
+        Status::NoInput(err) => err.clone(),
+        Status::Panicked(message) => format!("FAILED: {}", message),
+        Status::Todo => "TODO".to_string(),
+        Status::Timeout(budget) => format!("TIMEOUT after {:?}", budget),
+        _ => result.answer_text(),
+    }
+}
+
+pub fn text(run: &Run) -> String {
+    let mut output = String::new();
+
+    for result in &run.results {
+        let report = display_with_duration(
+            PRINT_WIDTH,
+            TIME_PAD_WIDTH,
+            result.is_multiline(),
+            format!("day{}pt{}: ", result.day, result.part).as_str(),
+            format!("{:<8} -", result.status).as_str(),
+            row_content(result).as_str(),
+            result.solve_time,
+        );
+
+        writeln!(output, "{}", report).unwrap();
+    }
+
+    writeln!(output).unwrap();
+
+    output += &timings(run);
+
+    writeln!(output).unwrap();
+
+    let memory_rows = memory_rows(run);
+
+    if !memory_rows.is_empty() {
+        output += &memory(&memory_rows);
+
+        writeln!(output).unwrap();
+    }
+
+    writeln!(
+        output,
+        "{}",
+        display_with_duration(
+            PRINT_WIDTH,
+            TIME_PAD_WIDTH,
+            false,
+            "> ",
+            "-",
+            "total thread time",
+            run.thread_time(),
+        )
+    )
+    .unwrap();
+
+    writeln!(
+        output,
+        "{}",
+        display_with_duration(
+            PRINT_WIDTH,
+            TIME_PAD_WIDTH,
+            false,
+            "> ",
+            "-",
+            "real time",
+            run.real_time,
+        )
+    )
+    .unwrap();
+
+    writeln!(output, "> schedule: {}", run.scheduling()).unwrap();
+
+    output
+}
+
+/// Table of parse and solve times, one row per day.
+fn timings(run: &Run) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "{:<10}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}",
+        "", "parse", "part 1", "part 2"
+    )
+    .unwrap();
+
+    for (day, parse_time) in run.parse_times() {
+        let solved = |result: &&SolveResult| {
+            result.day == day && !result.status.is_error() && result.status != Status::Cached
+        };
+
+        let parse_time = match run.results.iter().find(solved) {
+            Some(_) => format!("{:?}", parse_time),
+            None => "-".to_string(),
+        };
+
+        let part_time = |part: u8| {
+            run.results
+                .iter()
+                .filter(solved)
+                .find(|result| result.part == part)
+                .map(|result| format!("{:?}", result.solve_time))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        writeln!(
+            output,
+            "{:<10}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}",
+            format!("day{}", day),
+            parse_time,
+            part_time(1),
+            part_time(2)
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+/// Allocations per parse and part, labelled like `day1 parse` and `day1pt2`. Empty without
+/// the `count-allocations` feature.
+fn memory_rows(run: &Run) -> Vec<(String, Memory)> {
+    let mut rows = vec![];
+
+    for day in run.parse_times().into_keys() {
+        let results = run.results.iter().filter(|result| result.day == day);
+
+        if let Some(memory) = results.clone().find_map(|result| result.parse_memory) {
+            rows.push((timing_name(day, PARSE_PART), memory));
+        }
+
+        for result in results {
+            if let Some(memory) = result.solve_memory {
+                rows.push((timing_name(day, result.part), memory));
+            }
+        }
+    }
+
+    rows
+}
+
+/// Table of allocations, one row per parse and part.
+fn memory(rows: &[(String, Memory)]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "{:<15}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}",
+        "", "allocations", "allocated", "peak"
+    )
+    .unwrap();
+
+    for (label, memory) in rows {
+        writeln!(
+            output,
+            "{:<15}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}{:>TIME_PAD_WIDTH$}",
+            label,
+            memory.allocations,
+            Bytes(memory.allocated),
+            Bytes(memory.peak)
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+fn json_memory(memory: &Memory) -> String {
+    format!(
+        "{{\"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}}}",
+        memory.allocations, memory.allocated, memory.peak
+    )
+}
+
+pub fn json(run: &Run) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "{{").unwrap();
+    writeln!(output, "  \"real_time_ns\": {},", run.real_time.as_nanos()).unwrap();
+    writeln!(
+        output,
+        "  \"schedule\": {},",
+        json_string(&run.schedule.to_string())
+    )
+    .unwrap();
+    writeln!(output, "  \"threads\": {},", run.threads).unwrap();
+    writeln!(
+        output,
+        "  \"thread_time_ns\": {},",
+        run.thread_time().as_nanos()
+    )
+    .unwrap();
+    writeln!(output, "  \"results\": [").unwrap();
+
+    for (index, result) in run.results.iter().enumerate() {
+        let separator = if index + 1 < run.results.len() {
+            ","
+        } else {
+            ""
+        };
+
+        write!(output, "    {{").unwrap();
+        write!(output, "\"day\": {}, ", result.day).unwrap();
+        write!(output, "\"part\": {}, ", result.part).unwrap();
+        write!(output, "\"name\": {}, ", json_string(result.name)).unwrap();
+        write!(
+            output,
+            "\"answer\": {}, ",
+            json_answer(result.answer.as_ref())
+        )
+        .unwrap();
+        write!(
+            output,
+            "\"parse_time_ns\": {}, ",
+            result.parse_time.as_nanos()
+        )
+        .unwrap();
+        write!(
+            output,
+            "\"solve_time_ns\": {}, ",
+            result.solve_time.as_nanos()
+        )
+        .unwrap();
+        write!(
+            output,
+            "\"status\": {}",
+            json_string(&result.status.to_string())
+        )
+        .unwrap();
+
+        match &result.status {
+            Status::Fail(expected) => {
+                write!(output, ", \"expected\": {}", json_answer(Some(expected))).unwrap()
+            }
+            Status::NoInput(message) | Status::Panicked(message) => {
+                write!(output, ", \"message\": {}", json_string(message)).unwrap()
+            }
+            Status::Timeout(budget) => {
+                write!(output, ", \"timeout_ns\": {}", budget.as_nanos()).unwrap()
+            }
+            _ => {}
+        }
+
+        if let Some(memory) = &result.parse_memory {
+            write!(output, ", \"parse_memory\": {}", json_memory(memory)).unwrap();
+        }
+
+        if let Some(memory) = &result.solve_memory {
+            write!(output, ", \"solve_memory\": {}", json_memory(memory)).unwrap();
+        }
+
+        writeln!(output, "}}{}", separator).unwrap();
+    }
+
+    writeln!(output, "  ]").unwrap();
+    writeln!(output, "}}").unwrap();
+
+    output
+}
+
+pub fn csv(run: &Run) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "day,part,name,answer,parse_time_ns,solve_time_ns,status,expected,\
+         parse_allocations,parse_allocated_bytes,parse_peak_bytes,\
+         solve_allocations,solve_allocated_bytes,solve_peak_bytes"
+    )
+    .unwrap();
+
+    for result in &run.results {
+        writeln!(
+            output,
+            "{},{},{},{},{},{},{},{},{},{}",
+            result.day,
+            result.part,
+            csv_field(result.name),
+            csv_field(&result.answer_text()),
+            result.parse_time.as_nanos(),
+            result.solve_time.as_nanos(),
+            csv_field(&result.status.to_string()),
+            match &result.status {
+                Status::Fail(expected) => csv_field(&expected.to_string()),
+                _ => String::new(),
+            },
+            csv_memory(result.parse_memory),
+            csv_memory(result.solve_memory),
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+/// Empty fields without the `count-allocations` feature.
+fn csv_memory(memory: Option<Memory>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "{},{},{}",
+            memory.allocations, memory.allocated, memory.peak
+        ),
+        None => ",,".to_string(),
+    }
+}
+
+pub fn junit(run: &Run) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(
+        output,
+        "<testsuite name=\"aoc2022\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">",
+        run.results.len(),
+        run.failures(),
+        run.errors(),
+        run.results
+            .iter()
+            .filter(|result| result.status == Status::Todo)
+            .count(),
+        seconds(run.real_time)
+    )
+    .unwrap();
+    writeln!(output, "  <properties>").unwrap();
+    writeln!(
+        output,
+        "    <property name=\"schedule\" value=\"{}\"/>",
+        run.schedule
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "    <property name=\"threads\" value=\"{}\"/>",
+        run.threads
+    )
+    .unwrap();
+
+    // Parsing is shared by the parts, so it isn't part of their times.
+    for (day, parse_time) in run.parse_times() {
+        let parsed = run.results.iter().any(|result| {
+            result.day == day && !result.status.is_error() && result.status != Status::Cached
+        });
+
+        if parsed {
+            writeln!(
+                output,
+                "    <property name=\"day{}.parse_time\" value=\"{}\"/>",
+                day,
+                seconds(parse_time)
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(output, "  </properties>").unwrap();
+
+    for result in &run.results {
+        writeln!(
+            output,
+            "  <testcase classname=\"day{}\" name=\"part{}\" time=\"{}\">",
+            result.day,
+            result.part,
+            seconds(result.solve_time)
+        )
+        .unwrap();
+
+        match &result.status {
+            Status::Fail(expected) => writeln!(
+                output,
+                "    <failure type=\"{}\" message=\"expected {}\"/>",
+                xml_escape(&result.status.to_string()),
+                xml_escape(&expected.to_string())
+            )
+            .unwrap(),
+            Status::NoInput(message) | Status::Panicked(message) => writeln!(
+                output,
+                "    <error type=\"{}\" message=\"{}\"/>",
+                xml_escape(&result.status.to_string()),
+                xml_escape(message)
+            )
+            .unwrap(),
+            Status::Todo => writeln!(output, "    <skipped message=\"not implemented\"/>").unwrap(),
+            Status::Timeout(budget) => writeln!(
+                output,
+                "    <error type=\"{}\" message=\"exceeded {:?}\"/>",
+                result.status, budget
+            )
+            .unwrap(),
+            _ => {}
+        }
+
+        writeln!(
+            output,
+            "    <system-out>{}</system-out>",
+            xml_escape(&result.answer_text())
+        )
+        .unwrap();
+        writeln!(output, "  </testcase>").unwrap();
+    }
+
+    writeln!(output, "</testsuite>").unwrap();
+
+    output
+}
+
+pub const REPORT_TITLE: &str = "Advent of Code 2022";
+
+/// Answers spanning several lines, like the CRT output, are shown as visualizations
+/// below the results table instead of inside it.
+fn visualizations(run: &Run) -> impl Iterator<Item = &SolveResult> {
+    run.results.iter().filter(|result| result.is_multiline())
+}
+
+fn visualization_id(result: &SolveResult) -> String {
+    format!("day{}pt{}", result.day, result.part)
+}
+
+pub fn markdown(run: &Run, title: &str) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "# {}", title).unwrap();
+    writeln!(output).unwrap();
+    writeln!(
+        output,
+        "| Day | Part | Puzzle | Answer | Status | Parse | Solve |"
+    )
+    .unwrap();
+    writeln!(output, "|---:|---:|---|---|---|---:|---:|").unwrap();
+
+    for result in &run.results {
+        let answer = if result.is_multiline() {
+            format!("[see below](#{})", visualization_id(result))
+        } else {
+            match &result.status {
+                Status::Pass | Status::Unknown | Status::Cached => {
+                    format!("`{}`", result.answer_text())
+                }
+                _ => row_content(result),
+            }
+        };
+
+        writeln!(
+            output,
+            "| {} | {} | {} | {} | {} | {:?} | {:?} |",
+            result.day,
+            result.part,
+            markdown_cell(result.name),
+            markdown_cell(&answer),
+            result.status,
+            result.parse_time,
+            result.solve_time
+        )
+        .unwrap();
+    }
+
+    writeln!(output).unwrap();
+    writeln!(output, "**Total thread time:** {:?}  ", run.thread_time()).unwrap();
+    writeln!(output, "**Real time:** {:?}  ", run.real_time).unwrap();
+    writeln!(output, "**Schedule:** {}", run.scheduling()).unwrap();
+
+    let memory_rows = memory_rows(run);
+
+    if !memory_rows.is_empty() {
+        writeln!(output).unwrap();
+        writeln!(output, "## Memory").unwrap();
+        writeln!(output).unwrap();
+        writeln!(output, "| | Allocations | Allocated | Peak |").unwrap();
+        writeln!(output, "|---|---:|---:|---:|").unwrap();
+    }
+
+    for (label, memory) in &memory_rows {
+        writeln!(
+            output,
+            "| {} | {} | {} | {} |",
+            label,
+            memory.allocations,
+            Bytes(memory.allocated),
+            Bytes(memory.peak)
+        )
+        .unwrap();
+    }
+
+    let mut visualizations = visualizations(run).peekable();
+
+    if visualizations.peek().is_some() {
+        writeln!(output).unwrap();
+        writeln!(output, "## Visualizations").unwrap();
+    }
+
+    for result in visualizations {
+        writeln!(output).unwrap();
+        writeln!(
+            output,
+            "### <a id=\"{}\"></a>Day {} part {}: {}",
+            visualization_id(result),
+            result.day,
+            result.part,
+            result.name
+        )
+        .unwrap();
+        writeln!(output).unwrap();
+        writeln!(output, "```plaintext").unwrap();
+        writeln!(output, "{}", result.answer_text()).unwrap();
+        writeln!(output, "```").unwrap();
+
+        if let Status::Fail(expected) = &result.status {
+            writeln!(output).unwrap();
+            writeln!(output, "Expected:").unwrap();
+            writeln!(output).unwrap();
+            writeln!(output, "```plaintext").unwrap();
+            writeln!(output, "{}", expected).unwrap();
+            writeln!(output, "```").unwrap();
+        }
+    }
+
+    output
+}
+
+pub fn html(run: &Run, title: &str) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "<!DOCTYPE html>").unwrap();
+    writeln!(output, "<html lang=\"en\">").unwrap();
+    writeln!(output, "<head>").unwrap();
+    writeln!(output, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(output, "<title>{}</title>", xml_escape(title)).unwrap();
+    writeln!(output, "<style>{}</style>", HTML_STYLE).unwrap();
+    writeln!(output, "</head>").unwrap();
+    writeln!(output, "<body>").unwrap();
+    writeln!(output, "<h1>{}</h1>", xml_escape(title)).unwrap();
+    writeln!(output, "<table>").unwrap();
+    writeln!(
+        output,
+        "<tr><th>Day</th><th>Part</th><th>Puzzle</th><th>Answer</th><th>Status</th><th>Parse</th><th>Solve</th></tr>"
+    )
+    .unwrap();
+
+    for result in &run.results {
+        let answer = if result.is_multiline() {
+            format!("<a href=\"#{}\">see below</a>", visualization_id(result))
+        } else {
+            format!("<code>{}</code>", xml_escape(&row_content(result)))
+        };
+
+        writeln!(
+            output,
+            "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:?}</td><td>{:?}</td></tr>",
+            result.status.to_string().to_lowercase().replace(' ', "-"),
+            result.day,
+            result.part,
+            xml_escape(result.name),
+            answer,
+            result.status,
+            result.parse_time,
+            result.solve_time
+        )
+        .unwrap();
+    }
+
+    writeln!(output, "</table>").unwrap();
+    writeln!(
+        output,
+        "<p><strong>Total thread time:</strong> {:?}<br><strong>Real time:</strong> {:?}<br><strong>Schedule:</strong> {}</p>",
+        run.thread_time(),
+        run.real_time,
+        run.scheduling()
+    )
+    .unwrap();
+
+    let memory_rows = memory_rows(run);
+
+    if !memory_rows.is_empty() {
+        writeln!(output, "<h2>Memory</h2>").unwrap();
+        writeln!(output, "<table>").unwrap();
+        writeln!(
+            output,
+            "<tr><th></th><th>Allocations</th><th>Allocated</th><th>Peak</th></tr>"
+        )
+        .unwrap();
+
+        for (label, memory) in &memory_rows {
+            writeln!(
+                output,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                label,
+                memory.allocations,
+                Bytes(memory.allocated),
+                Bytes(memory.peak)
+            )
+            .unwrap();
+        }
+
+        writeln!(output, "</table>").unwrap();
+    }
+
+    for result in visualizations(run) {
+        writeln!(
+            output,
+            "<h2 id=\"{}\">Day {} part {}: {}</h2>",
+            visualization_id(result),
+            result.day,
+            result.part,
+            xml_escape(result.name)
+        )
+        .unwrap();
+        writeln!(output, "<pre>{}</pre>", xml_escape(&result.answer_text())).unwrap();
+
+        if let Status::Fail(expected) = &result.status {
+            writeln!(output, "<p>Expected:</p>").unwrap();
+            writeln!(output, "<pre>{}</pre>", xml_escape(&expected.to_string())).unwrap();
+        }
+    }
+
+    writeln!(output, "</body>").unwrap();
+    writeln!(output, "</html>").unwrap();
+
+    output
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td:nth-child(-n+2), td:nth-child(n+6) { text-align: right; }
+pre { background: #0f0f23; color: #ffff66; padding: 1em; display: inline-block; }
+.pass td:nth-child(5) { color: #090; }
+.fail td:nth-child(5), .failed td:nth-child(5), .timeout td:nth-child(5) { color: #c00; }
+.unknown td:nth-child(5), .todo td:nth-child(5), .no-input td:nth-child(5) { color: #888; }";
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn verification(run: &Run) -> String {
+    let mut output = String::new();
+
+    for result in &run.results {
+        let prefix = format!("day{}pt{}", result.day, result.part);
+
+        match &result.status {
+            Status::Fail(expected) => writeln!(
+                output,
+                "{:<10}{:<9}expected {}, got {}",
+                prefix,
+                result.status,
+                expected,
+                result.answer_text()
+            ),
+            Status::NoInput(message) | Status::Panicked(message) => {
+                writeln!(output, "{:<10}{:<9}{}", prefix, result.status, message)
+            }
+            Status::Timeout(budget) => writeln!(
+                output,
+                "{:<10}{:<9}exceeded {:?}",
+                prefix, result.status, budget
+            ),
+            _ => writeln!(output, "{:<10}{}", prefix, result.status),
+        }
+        .unwrap();
+    }
+
+    let count = |matches: fn(&Status) -> bool| {
+        run.results
+            .iter()
+            .filter(|result| matches(&result.status))
+            .count()
+    };
+
+    writeln!(output).unwrap();
+    writeln!(
+        output,
+        "{} passed, {} cached, {} failed, {} unknown, {} without input, {} panicked, {} todo, {} timed out",
+        count(|status| matches!(status, Status::Pass)),
+        count(|status| matches!(status, Status::Cached)),
+        count(|status| matches!(status, Status::Fail(_))),
+        count(|status| matches!(status, Status::Unknown)),
+        count(|status| matches!(status, Status::NoInput(_))),
+        count(|status| matches!(status, Status::Panicked(_))),
+        count(|status| matches!(status, Status::Todo)),
+        count(|status| matches!(status, Status::Timeout(_)))
+    )
+    .unwrap();
+
+    output
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.6}", duration.as_secs_f64())
+}
+
+/// Integers as numbers and bitmaps as arrays of rows, so the answers keep their type.
+fn json_answer(answer: Option<&Answer>) -> String {
+    match answer {
+        None => "null".to_string(),
+        Some(Answer::Integer(integer)) => integer.to_string(),
+        Some(Answer::Text(text)) => json_string(text),
+        Some(bitmap @ Answer::Bitmap(_)) => {
+            let rows: Vec<String> = bitmap.to_string().lines().map(json_string).collect();
+            format!("[{}]", rows.join(", "))
+        }
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut output = String::from("\"");
+
+    for char in value.chars() {
+        match char {
+            '"' => output += "\\\"",
+            '\\' => output += "\\\\",
+            '\n' => output += "\\n",
+            '\r' => output += "\\r",
+            '\t' => output += "\\t",
+            char if (char as u32) < 0x20 => write!(output, "\\u{:04x}", char as u32).unwrap(),
+            char => output.push(char),
+        }
+    }
+
+    output.push('"');
+    output
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(['"', ',', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn xml_escape(value: &str) -> String {
+    let mut output = String::new();
+
+    for char in value.chars() {
+        match char {
+            '&' => output += "&amp;",
+            '<' => output += "&lt;",
+            '>' => output += "&gt;",
+            '"' => output += "&quot;",
+            '\'' => output += "&apos;",
+            char => output.push(char),
+        }
+    }
+
+    output
+}
+
+pub fn display_with_duration(
+    target_len: usize,
+    time_len: usize,
+    multiline: bool,
+    prefix: &str,
+    suffix: &str,
+    content: &str,
+    duration: Duration,
+) -> String {
+    let mut output = String::new();
+    output += prefix;
+    if multiline {
+        output += "\n";
+    }
+    let duration_str = format!("{:?}", duration);
+    let content_len = content.lines().map(|line| line.len()).max().unwrap_or(0);
+    let pad_len = target_len
+        .saturating_sub(if !multiline { prefix.len() } else { 0 })
+        .saturating_sub(content_len)
+        .saturating_sub(suffix.len());
+
+    let main_pad = " ".repeat(pad_len.saturating_sub(time_len).max(1));
+    let time_pad = " ".repeat(time_len.saturating_sub(duration_str.len()));
+    output += format!(
+        "{}{}{}{}{}",
+        content, main_pad, suffix, time_pad, duration_str
+    )
+    .as_str();
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{csv_field, html, json_string, markdown, xml_escape};
+    use crate::{
+        answer::Answer,
+        runner::{Run, Schedule, SolveResult, Status},
+    };
+
+    fn run() -> Run {
+        let result = |day: u8, part: u8, answer: Answer, status: Status| {
+            let solve_time = Duration::from_micros(part as u64);
+            SolveResult::fixture(
+                day,
+                part,
+                Some(answer),
+                status,
+                Duration::from_micros(30),
+                solve_time,
+            )
+        };
+
+        Run {
+            results: vec![
+                result(10, 1, Answer::Integer(13140), Status::Pass),
+                result(
+                    10,
+                    2,
+                    Answer::bitmap("#..#\n.##."),
+                    Status::Fail(Answer::bitmap("#..#\n#..#")),
+                ),
+                result(11, 1, Answer::from("a|b"), Status::Unknown),
+            ],
+            real_time: Duration::from_micros(50),
+            schedule: Schedule::Parallel,
+            threads: 2,
+        }
+    }
+
+    #[test]
+    fn escapes_multiline_answers() {
+        let crt = "#..#\n.##.";
+
+        assert_eq!(json_string(crt), "\"#..#\\n.##.\"");
+        assert_eq!(csv_field(crt), "\"#..#\n.##.\"");
+        assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+        assert_eq!(xml_escape("<a & 'b'>"), "&lt;a &amp; &apos;b&apos;&gt;");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let output = markdown(&run(), "Day <1>");
+
+        assert!(output.starts_with("# Day <1>\n"));
+        assert!(output.contains("| 10 | 1 | Cathode-Ray Tube | `13140` | PASS | 30µs | 1µs |\n"));
+        assert!(output.contains("| 10 | 2 | Cathode-Ray Tube | [see below](#day10pt2) | FAIL |"));
+        assert!(output.contains("| `a\\|b` |"));
+        assert!(output.contains("**Schedule:** parallel, 2 threads\n"));
+        assert!(output.contains("### <a id=\"day10pt2\"></a>Day 10 part 2"));
+        assert!(output.contains("```plaintext\n#..#\n.##.\n```\n"));
+        assert!(output.contains("Expected:\n\n```plaintext\n#..#\n#..#\n```\n"));
+    }
+
+    #[test]
+    fn renders_html() {
+        let output = html(&run(), "Day <1>");
+
+        assert!(output.starts_with("<!DOCTYPE html>\n"));
+        assert!(output.contains("<title>Day &lt;1&gt;</title>"));
+        assert!(output.contains("<tr class=\"pass\"><td>10</td><td>1</td>"));
+        assert!(output.contains("<a href=\"#day10pt2\">see below</a>"));
+        assert!(output.contains("<code>a|b</code>"));
+        assert!(output.contains("<h2 id=\"day10pt2\">Day 10 part 2: Cathode-Ray Tube</h2>"));
+        assert!(output.contains("<pre>#..#\n.##.</pre>"));
+        assert!(output.contains("<p>Expected:</p>\n<pre>#..#\n#..#</pre>"));
+        assert!(output.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,741 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::BTreeMap,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::{
+    answer::Answer,
+    baseline::PARSE_PART,
+    cache::{self, Cache},
+    extra::{self, Visuals},
+    input::Inputs,
+    memory::{self, Memory},
+    solution::{DynSolution, Params},
+};
+
+#[derive(Clone, Copy)]
+pub struct Job {
+    pub solution: &'static dyn DynSolution,
+    pub part: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// Wrong answer, with the expected one.
+    Fail(Answer),
+    Unknown,
+    NoInput(String),
+    Panicked(String),
+    Todo,
+    Timeout(Duration),
+    /// Answer taken from the cache instead of solving.
+    Cached,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail(_))
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Status::NoInput(_) | Status::Panicked(_) | Status::Timeout(_)
+        )
+    }
+
+    /// Whether the solution itself misbehaved, as opposed to missing its input.
+    pub fn is_crash(&self) -> bool {
+        matches!(self, Status::Panicked(_) | Status::Timeout(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Status::Pass => "PASS",
+            Status::Fail(_) => "FAIL",
+            Status::Unknown => "UNKNOWN",
+            Status::NoInput(_) => "NO INPUT",
+            Status::Panicked(_) => "FAILED",
+            Status::Todo => "TODO",
+            Status::Timeout(_) => "TIMEOUT",
+            Status::Cached => "CACHED",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SolveResult {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    /// `None` when the part couldn't be solved, see the status.
+    pub answer: Option<Answer>,
+    /// Time spent parsing the input, shared by all parts of the day.
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Allocations while parsing, shared like `parse_time`. Only counted with the
+    /// `count-allocations` feature.
+    pub parse_memory: Option<Memory>,
+    pub solve_memory: Option<Memory>,
+    pub status: Status,
+}
+
+impl SolveResult {
+    pub fn duration(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+
+    /// The answer as displayed, empty without one.
+    pub fn answer_text(&self) -> String {
+        self.answer
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.answer.as_ref().is_some_and(Answer::is_multiline)
+    }
+}
+
+#[cfg(test)]
+impl SolveResult {
+    /// A finished result for the tests of the reports, named after the day's puzzle.
+    pub fn fixture(
+        day: u8,
+        part: u8,
+        answer: Option<Answer>,
+        status: Status,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) -> Self {
+        SolveResult {
+            day,
+            part,
+            name: crate::registry::find(day).map_or("", |solution| solution.name()),
+            answer,
+            parse_time,
+            solve_time,
+            parse_memory: None,
+            solve_memory: None,
+            status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Schedule {
+    /// One part at a time on a single thread, for timings without contention.
+    Sequential,
+    /// Days in parallel, and the parts of a day in parallel once it is parsed.
+    #[default]
+    Parallel,
+    /// Like [`Schedule::Parallel`], but the days that took longest in the past start first.
+    LongestFirst,
+}
+
+impl TryFrom<&str> for Schedule {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "sequential" => Ok(Schedule::Sequential),
+            "parallel" => Ok(Schedule::Parallel),
+            "longest-first" => Ok(Schedule::LongestFirst),
+            _ => Err(format!(
+                "invalid schedule '{}', expected sequential, parallel or longest-first",
+                value
+            )),
+        }
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Schedule::Sequential => "sequential",
+            Schedule::Parallel => "parallel",
+            Schedule::LongestFirst => "longest-first",
+        })
+    }
+}
+
+pub struct Run {
+    pub results: Vec<SolveResult>,
+    pub real_time: Duration,
+    pub schedule: Schedule,
+    pub threads: usize,
+}
+
+impl Run {
+    /// The schedule and thread count, e.g. `parallel, 4 threads`.
+    pub fn scheduling(&self) -> String {
+        match self.threads {
+            1 => format!("{}, 1 thread", self.schedule),
+            threads => format!("{}, {} threads", self.schedule, threads),
+        }
+    }
+
+    pub fn parse_times(&self) -> BTreeMap<u8, Duration> {
+        self.results
+            .iter()
+            .map(|result| (result.day, result.parse_time))
+            .collect()
+    }
+
+    pub fn thread_time(&self) -> Duration {
+        let parse_time: Duration = self.parse_times().values().sum();
+        let solve_time: Duration = self.results.iter().map(|result| result.solve_time).sum();
+
+        parse_time + solve_time
+    }
+
+    pub fn failures(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.status.is_failure())
+            .count()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.status.is_error())
+            .count()
+    }
+
+    pub fn crashes(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.status.is_crash())
+            .count()
+    }
+}
+
+/// Where panics happened by message, recorded by the hook from [`install_panic_hook`] on the
+/// thread that panicked and taken by [`catch_panic`] on the one that catches it, which isn't
+/// the same thread when the panic comes from a rayon worker.
+static PANIC_LOCATIONS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    /// Above zero while panics on this thread end up in a report, so printing them would
+    /// only clutter it.
+    static QUIET: Cell<usize> = const { Cell::new(0) };
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+/// Replaces the default panic output with recording the location for [`catch_panic`], on
+/// threads that solve puzzles. Panics anywhere else are still printed. Only installed once,
+/// later calls do nothing.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if let (Some(message), Some(location)) =
+                (panic_message(info.payload()), info.location())
+            {
+                PANIC_LOCATIONS
+                    .lock()
+                    .unwrap()
+                    .insert(message, location.to_string());
+            }
+
+            if QUIET.with(Cell::get) == 0 {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into a [`Status::Panicked`] or, for `todo!()` and
+/// `unimplemented!()`, a [`Status::Todo`].
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Status> {
+    QUIET.with(|quiet| quiet.set(quiet.get() + 1));
+    let output = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(quiet.get() - 1));
+
+    output.map_err(|payload| {
+        let message =
+            panic_message(payload.as_ref()).unwrap_or_else(|| "unknown panic".to_string());
+
+        if message.starts_with("not yet implemented") || message.starts_with("not implemented") {
+            return Status::Todo;
+        }
+
+        match PANIC_LOCATIONS.lock().unwrap().remove(&message) {
+            Some(location) => Status::Panicked(format!("{} ({})", message, location)),
+            None => Status::Panicked(message),
+        }
+    })
+}
+
+/// A pool for solving, whose workers are quiet about panics like [`catch_panic`], since
+/// panics on them end up in a `catch_panic` on the thread that started the work.
+pub fn solver_pool(threads: Option<usize>) -> ThreadPool {
+    let mut builder = ThreadPoolBuilder::new().start_handler(|_| QUIET.with(|quiet| quiet.set(1)));
+
+    if let Some(threads) = threads {
+        builder = builder.num_threads(threads);
+    }
+
+    builder.build().expect("build thread pool")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// Time budget for parsing and for each part, `None` waits forever.
+    pub timeout: Option<Duration>,
+    pub schedule: Schedule,
+    /// Days solved at the same time in the parallel schedules, and the workers of each
+    /// solution, `None` uses one per CPU.
+    pub threads: Option<usize>,
+    /// Past timings per day and part, with parsing as [`PARSE_PART`], used to order
+    /// [`Schedule::LongestFirst`].
+    pub estimates: BTreeMap<(u8, u8), Duration>,
+    /// Answers from earlier runs to use instead of solving, `None` solves everything.
+    pub cache: Option<Cache>,
+    /// Passed to `Solution::configure` after parsing.
+    pub params: Params,
+    /// Where and how the solutions visualize during the run.
+    pub visuals: Visuals,
+    /// Receives a [`Progress`] event whenever a day or part starts or finishes.
+    pub progress: Option<mpsc::Sender<Progress>>,
+}
+
+impl Settings {
+    fn notify(&self, progress: Progress) {
+        if let Some(sender) = &self.progress {
+            // Nobody listening anymore is fine, the run goes on without them.
+            _ = sender.send(progress);
+        }
+    }
+
+    fn finished(&self, results: &[SolveResult]) {
+        for result in results {
+            self.notify(Progress::Finished(result.clone()));
+        }
+    }
+}
+
+/// What the runner is doing, for showing a run while it happens.
+#[derive(Debug, Clone)]
+pub enum Progress {
+    Parsing { day: u8 },
+    Solving { day: u8, part: u8 },
+    Finished(SolveResult),
+}
+
+/// Runs `f` within the time budget and measures how long it took and what it allocated.
+/// A solution that runs out of time is left running on its own thread, there is no way to
+/// stop it.
+///
+/// `f` runs on a pool of its own with `threads` workers, so that rayon inside the solution
+/// is limited to them, no other solution's work is timed along with it, and one that is left
+/// running keeps no worker from the solutions after it.
+fn timed<T>(
+    budget: Option<Duration>,
+    threads: usize,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> (Result<T, Status>, Duration, Option<Memory>)
+where
+    T: Send + 'static,
+{
+    let pool = solver_pool(Some(threads));
+
+    let measure = move || {
+        pool.install(|| {
+            let start = Instant::now();
+            let (output, memory) = memory::measure(|| catch_panic(f));
+            (output, start.elapsed(), memory)
+        })
+    };
+
+    let Some(budget) = budget else {
+        return measure();
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || sender.send(measure()));
+
+    receiver
+        .recv_timeout(budget)
+        .unwrap_or((Err(Status::Timeout(budget)), budget, None))
+}
+
+/// Like [`timed`] with the budget of the run. Memory isn't measured anymore once a solution
+/// runs out of time, it keeps allocating in the background.
+fn timed_solution<T>(
+    settings: &Settings,
+    threads: usize,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> (Result<T, Status>, Duration, Option<Memory>)
+where
+    T: Send + 'static,
+{
+    let visuals = settings.visuals.clone();
+    let output = timed(settings.timeout, threads, move || {
+        extra::with_visuals(visuals, f)
+    });
+
+    if let (Err(Status::Timeout(_)), _, _) = output {
+        memory::abandon();
+    }
+
+    output
+}
+
+/// Estimated time for a day, `None` when it has no past timings.
+fn estimate(jobs: &[Job], settings: &Settings) -> Option<Duration> {
+    let day = jobs[0].solution.day();
+
+    let parse_time = settings
+        .estimates
+        .get(&(day, PARSE_PART))
+        .copied()
+        .unwrap_or_default();
+
+    let solve_time: Duration = jobs
+        .iter()
+        .map(|job| settings.estimates.get(&(day, job.part)).copied())
+        .sum::<Option<Duration>>()?;
+
+    Some(parse_time + solve_time)
+}
+
+pub fn run(jobs: &[Job], inputs: &Inputs, settings: &Settings) -> Run {
+    let start = Instant::now();
+
+    let mut days: Vec<Vec<Job>> = vec![];
+
+    for job in jobs {
+        match days.last_mut() {
+            Some(day) if day[0].solution.day() == job.solution.day() => day.push(*job),
+            _ => days.push(vec![*job]),
+        }
+    }
+
+    // Allocation counters are global, so days can't share them.
+    let schedule = if memory::ENABLED {
+        Schedule::Sequential
+    } else {
+        settings.schedule
+    };
+
+    let threads = match schedule {
+        Schedule::Sequential => 1,
+        Schedule::Parallel | Schedule::LongestFirst => settings
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from)),
+    };
+
+    let mut order: Vec<usize> = (0..days.len()).collect();
+
+    if schedule == Schedule::LongestFirst {
+        // Days without past timings could be anything, start them first to be safe.
+        order.sort_by_key(|&index| {
+            std::cmp::Reverse(estimate(&days[index], settings).unwrap_or(Duration::MAX))
+        });
+    }
+
+    let mut results: Vec<(usize, Vec<SolveResult>)> = match schedule {
+        Schedule::Sequential => order
+            .iter()
+            .map(|&index| {
+                (
+                    index,
+                    solve_day(&days[index], inputs, settings, threads, false),
+                )
+            })
+            .collect(),
+        Schedule::Parallel | Schedule::LongestFirst => {
+            // Days wait for their solutions on threads of their own and take the next one in
+            // order when done, so they start in the order given.
+            let next = AtomicUsize::new(0);
+            let results = Mutex::new(vec![]);
+
+            thread::scope(|scope| {
+                for _ in 0..threads.min(order.len()) {
+                    scope.spawn(|| {
+                        while let Some(&index) = order.get(next.fetch_add(1, Ordering::SeqCst)) {
+                            let day = solve_day(&days[index], inputs, settings, threads, true);
+                            results.lock().unwrap().push((index, day));
+                        }
+                    });
+                }
+            });
+
+            results.into_inner().unwrap()
+        }
+    };
+
+    results.sort_by_key(|(index, _)| *index);
+
+    Run {
+        results: results
+            .into_iter()
+            .flat_map(|(_, results)| results)
+            .collect(),
+        real_time: start.elapsed(),
+        schedule,
+        threads,
+    }
+}
+
+/// Parses the input of a day once and solves the requested parts from the same model, on
+/// pools of `threads` workers. The parts run one after the other unless `parallel`.
+fn solve_day(
+    jobs: &[Job],
+    inputs: &Inputs,
+    settings: &Settings,
+    threads: usize,
+    parallel: bool,
+) -> Vec<SolveResult> {
+    let solution = jobs[0].solution;
+
+    let mut results: Vec<SolveResult> = jobs
+        .iter()
+        .map(|job| SolveResult {
+            day: solution.day(),
+            part: job.part,
+            name: solution.name(),
+            answer: None,
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            parse_memory: None,
+            solve_memory: None,
+            status: Status::Unknown,
+        })
+        .collect();
+
+    let input = match inputs.load(solution.day()) {
+        Ok(input) => input,
+        Err(err) => {
+            for result in &mut results {
+                result.status = Status::NoInput(err.clone());
+            }
+            settings.finished(&results);
+            return results;
+        }
+    };
+
+    if let Some(cache) = &settings.cache {
+        let hash = cache::hash(&input);
+
+        for result in &mut results {
+            if let Some(answer) = cache.get(result.day, result.part, solution.version(), hash) {
+                result.answer = Some(answer.clone());
+                result.status = Status::Cached;
+            }
+        }
+
+        if results.iter().all(|result| result.status == Status::Cached) {
+            settings.finished(&results);
+            return results;
+        }
+    }
+
+    let pending = |result: &&mut SolveResult| result.status != Status::Cached;
+
+    settings.finished(
+        &results
+            .iter()
+            .filter(|result| result.status == Status::Cached)
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    settings.notify(Progress::Parsing {
+        day: solution.day(),
+    });
+
+    let (model, parse_time, parse_memory) =
+        timed_solution(settings, threads, move || solution.parse(&input));
+
+    let model = match model.and_then(|mut model| {
+        solution
+            .configure(&mut model, &settings.params)
+            .map(|()| model)
+            .map_err(|err| Status::Panicked(format!("invalid parameters: {}", err)))
+    }) {
+        Ok(model) => Arc::new(model),
+        Err(status) => {
+            for result in results.iter_mut().filter(pending) {
+                result.parse_time = parse_time;
+                result.parse_memory = parse_memory;
+                result.status = status.clone();
+                settings.notify(Progress::Finished(result.clone()));
+            }
+            return results;
+        }
+    };
+
+    let solve = |result: &mut SolveResult| {
+        let model = model.clone();
+        let part = result.part;
+
+        settings.notify(Progress::Solving {
+            day: result.day,
+            part,
+        });
+
+        let (answer, solve_time, solve_memory) =
+            timed_solution(settings, threads, move || solution.solve(part, &model));
+
+        match answer {
+            Ok(answer) => result.answer = Some(answer),
+            Err(status) => result.status = status,
+        }
+        result.solve_time = solve_time;
+        result.solve_memory = solve_memory;
+        result.parse_time = parse_time;
+        result.parse_memory = parse_memory;
+
+        settings.finished(std::slice::from_ref(result));
+    };
+
+    if parallel {
+        let solve = &solve;
+
+        thread::scope(|scope| {
+            for result in results.iter_mut().filter(pending) {
+                scope.spawn(move || solve(result));
+            }
+        });
+    } else {
+        results.iter_mut().filter(pending).for_each(solve);
+    }
+
+    results
+}
+
+#[test]
+fn catches_panics() {
+    install_panic_hook();
+
+    assert_eq!(catch_panic(|| 42), Ok(42));
+    assert_eq!(catch_panic(|| -> u8 { todo!() }), Err(Status::Todo));
+    assert_eq!(
+        catch_panic(|| -> u8 { unimplemented!("part 2") }),
+        Err(Status::Todo)
+    );
+    assert!(matches!(
+        catch_panic(|| -> u8 { panic!("boom") }),
+        Err(Status::Panicked(message)) if message.starts_with("boom (src/runner.rs:")
+    ));
+
+    let pool = solver_pool(Some(1));
+
+    assert!(matches!(
+        catch_panic(|| pool.install(|| -> u8 { panic!("boom on a worker") })),
+        Err(Status::Panicked(message)) if message.starts_with("boom on a worker (src/runner.rs:")
+    ));
+}
+
+#[test]
+fn times_out_slow_solutions() {
+    let budget = Duration::from_millis(20);
+
+    let (output, time, memory) = timed(Some(budget), 1, || {
+        thread::sleep(Duration::from_secs(1));
+    });
+
+    assert_eq!(output, Err(Status::Timeout(budget)));
+    assert_eq!(time, budget);
+    assert_eq!(memory, None);
+
+    // The solution that timed out still sleeps, but on a worker of its own.
+    let (output, _, _) = timed(Some(Duration::from_secs(10)), 1, || 42);
+
+    assert_eq!(output, Ok(42));
+}
+
+#[test]
+fn solves_on_a_pool_of_its_own() {
+    let (output, _, _) = timed(Some(Duration::from_secs(10)), 1, || {
+        (rayon::current_thread_index(), rayon::current_num_threads())
+    });
+
+    assert_eq!(output, Ok((Some(0), 1)));
+
+    let (output, _, _) = timed(None, 3, rayon::current_num_threads);
+
+    assert_eq!(output, Ok(3));
+}
+
+// Timing out stops counting allocations for good, which would break the memory tests.
+/// Sleeps through part 1 and answers part 2 right away.
+#[cfg(all(test, not(feature = "count-allocations")))]
+struct Sleepy;
+
+#[cfg(all(test, not(feature = "count-allocations")))]
+impl crate::solution::Solution for Sleepy {
+    type Model = ();
+
+    fn name(&self) -> &'static str {
+        "Sleepy"
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, _input: &str) -> Self::Model {}
+
+    fn part1(&self, _model: &Self::Model) -> Answer {
+        thread::sleep(Duration::from_secs(2));
+        Answer::from(1u32)
+    }
+
+    fn part2(&self, _model: &Self::Model) -> Answer {
+        Answer::from(2u32)
+    }
+}
+
+#[cfg(not(feature = "count-allocations"))]
+#[test]
+fn solves_on_after_a_timeout() {
+    let jobs = [1, 2].map(|part| Job {
+        solution: &Sleepy,
+        part,
+    });
+    let inputs = Inputs::from(crate::input::InputSource::File(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").into(),
+    ));
+    let settings = Settings {
+        timeout: Some(Duration::from_millis(50)),
+        schedule: Schedule::Sequential,
+        ..Settings::default()
+    };
+
+    let run = run(&jobs, &inputs, &settings);
+
+    assert_eq!(
+        run.results[0].status,
+        Status::Timeout(Duration::from_millis(50))
+    );
+    assert_eq!(run.results[1].status, Status::Unknown);
+    assert_eq!(run.results[1].answer, Some(Answer::from(2u32)));
+}
@@ -0,0 +1,215 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("day.template.rs");
+
+/// Creates `src/dayN.rs` from the template, registers it and adds placeholder inputs and an
+/// expected-answer file, so the example test fails until the day is solved. Returns the files
+/// that were created or changed.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let module = root.join("src").join(format!("day{}.rs", day));
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("registry.rs");
+
+    let lib_content = register_module(&read(&lib)?, day)?;
+    let registry_content = register_solution(&read(&registry)?, day)?;
+
+    write(&module, &render_template(day))?;
+    write(&lib, &lib_content)?;
+    write(&registry, &registry_content)?;
+
+    let mut changed = vec![module, lib, registry];
+
+    let placeholders = [
+        ("input", format!("day{}.txt", day), String::new()),
+        ("example_input", format!("day{}.txt", day), String::new()),
+        (
+            "example_input",
+            format!("day{}.expected", day),
+            expected_template(day),
+        ),
+    ];
+
+    for (directory, name, content) in placeholders {
+        let path = root.join(directory).join(name);
+
+        if !path.exists() {
+            fs::create_dir_all(root.join(directory))
+                .map_err(|err| format!("{}: {}", directory, err))?;
+            write(&path, &content)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+pub fn render_template(day: u8) -> String {
+    TEMPLATE
+        .replace("DayN", &format!("Day{}", day))
+        .lines()
+        .map(|line| {
+            if line.trim() == "N" {
+                line.replace('N', &day.to_string())
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn expected_template(day: u8) -> String {
+    format!(
+        "# Answers for example_input/day{}.txt, see src/examples.rs for the format\npart1:\npart2:\n",
+        day
+    )
+}
+
+fn day_number(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    line.strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok()
+}
+
+/// Adds `pub mod dayN;` to the block of day modules, keeping rustfmt's ordering.
+fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let lines: Vec<&str> = lib.lines().collect();
+
+    let first = lines
+        .iter()
+        .position(|line| day_number(line, "pub mod day", ";").is_some())
+        .ok_or("no day modules found in lib.rs")?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| day_number(line, "pub mod day", ";").is_some())
+        .count();
+
+    let mut modules: Vec<String> = lines[first..first + count]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    modules.push(format!("pub mod day{};", day));
+    modules.sort_by_key(|module| module.trim_end_matches(';').to_string());
+
+    let mut output: Vec<String> = lines[..first].iter().map(|line| line.to_string()).collect();
+    output.extend(modules);
+    output.extend(lines[first + count..].iter().map(|line| line.to_string()));
+
+    Ok(output.join("\n") + "\n")
+}
+
+/// Adds the solution to the registry imports and to `SOLUTIONS` in day order.
+fn register_solution(registry: &str, day: u8) -> Result<String, String> {
+    let (imports, rest) = registry
+        .strip_prefix("use crate::{")
+        .and_then(|rest| rest.split_once("};"))
+        .ok_or("unexpected import in registry.rs")?;
+
+    let mut items: Vec<String> = imports
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect();
+    items.push(format!("day{}", day));
+    items.sort();
+
+    let mut import = format!("use crate::{{{}}};", items.join(", "));
+
+    if import.len() > 100 {
+        import = String::from("use crate::{\n");
+        let mut line = String::from("   ");
+
+        for item in items {
+            if line.len() + item.len() + 2 > 100 {
+                import += &line;
+                import += "\n";
+                line = String::from("   ");
+            }
+            line += &format!(" {},", item);
+        }
+
+        import += &line;
+        import += "\n};";
+    }
+
+    let lines: Vec<&str> = rest.lines().collect();
+    let entry = |line: &str| line.trim().starts_with("&day");
+
+    let first = lines
+        .iter()
+        .position(|line| entry(line))
+        .ok_or("no solutions found in registry.rs")?;
+    let count = lines[first..].iter().take_while(|line| entry(line)).count();
+
+    let mut solutions: Vec<(u8, String)> = lines[first..first + count]
+        .iter()
+        .map(|line| {
+            let number = line
+                .trim()
+                .trim_start_matches("&day")
+                .split("::")
+                .next()
+                .and_then(|number| number.parse().ok())
+                .unwrap_or(0);
+            (number, line.to_string())
+        })
+        .collect();
+    solutions.push((day, format!("    &day{0}::Day{0},", day)));
+    solutions.sort();
+
+    let mut output: Vec<String> = lines[..first].iter().map(|line| line.to_string()).collect();
+    output.extend(solutions.into_iter().map(|(_, line)| line));
+    output.extend(lines[first + count..].iter().map(|line| line.to_string()));
+
+    Ok(import + &output.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{register_module, register_solution, render_template};
+
+    #[test]
+    fn renders_template() {
+        let module = render_template(20);
+
+        assert!(module.contains("pub struct Day20;"));
+        assert!(module.contains("impl Solution for Day20 {"));
+        assert!(module.contains("        20\n"));
+        assert!(module.contains("\"Day20\""));
+        assert!(!module.contains("DayN"));
+    }
+
+    #[test]
+    fn registers_day() {
+        let lib = "//! Docs\n\npub mod day1;\npub mod day10;\npub mod day2;\n\npub mod cli;\n";
+        assert_eq!(
+            register_module(lib, 11).unwrap(),
+            "//! Docs\n\npub mod day1;\npub mod day10;\npub mod day11;\npub mod day2;\n\npub mod cli;\n"
+        );
+
+        let registry = "use crate::{day1, day10, day2, solution::DynSolution};\n\npub static SOLUTIONS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day10::Day10,\n];\n";
+        assert_eq!(
+            register_solution(registry, 3).unwrap(),
+            "use crate::{day1, day10, day2, day3, solution::DynSolution};\n\npub static SOLUTIONS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n    &day10::Day10,\n];\n"
+        );
+    }
+}
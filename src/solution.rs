@@ -0,0 +1,81 @@
+use std::{any::Any, collections::BTreeMap};
+
+use crate::answer::Answer;
+
+pub type Model = Box<dyn Any + Send + Sync>;
+
+/// Named values that change how a puzzle is solved, such as the smaller search area of an example.
+pub type Params = BTreeMap<String, String>;
+
+pub trait Solution: Sync {
+    type Model: Send + Sync + 'static;
+
+    fn name(&self) -> &'static str;
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Self::Model;
+    fn part1(&self, model: &Self::Model) -> Answer;
+    fn part2(&self, model: &Self::Model) -> Answer;
+
+    /// Bump when the answers could change, so cached answers of older versions are ignored.
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn configure(&self, _model: &mut Self::Model, params: &Params) -> Result<(), String> {
+        match params.keys().next() {
+            Some(key) => Err(format!("unknown parameter '{}'", key)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Type erased [`Solution`], so solutions with different models can share the registry.
+pub trait DynSolution: Sync {
+    fn name(&self) -> &'static str;
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Model;
+    fn solve(&self, part: u8, model: &Model) -> Answer;
+    fn version(&self) -> u32;
+    fn configure(&self, model: &mut Model, params: &Params) -> Result<(), String>;
+}
+
+impl<T> DynSolution for T
+where
+    T: Solution,
+{
+    fn name(&self) -> &'static str {
+        Solution::name(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn parse(&self, input: &str) -> Model {
+        Box::new(Solution::parse(self, input))
+    }
+
+    fn solve(&self, part: u8, model: &Model) -> Answer {
+        let model = model
+            .downcast_ref::<T::Model>()
+            .expect("model parsed by the same solution");
+
+        match part {
+            1 => self.part1(model),
+            2 => self.part2(model),
+            _ => unreachable!(),
+        }
+    }
+
+    fn version(&self) -> u32 {
+        Solution::version(self)
+    }
+
+    fn configure(&self, model: &mut Model, params: &Params) -> Result<(), String> {
+        let model = model
+            .downcast_mut::<T::Model>()
+            .expect("model parsed by the same solution");
+
+        Solution::configure(self, model, params)
+    }
+}
@@ -0,0 +1,496 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, IsTerminal, Read, Write},
+    process::{Command, Stdio},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::Answers,
+    extra::{Frames, Sink, Visuals},
+    input::Inputs,
+    report::{self, PRINT_WIDTH, TIME_PAD_WIDTH},
+    runner::{self, Job, Progress, Run, Settings, SolveResult, Status},
+};
+
+const TTY: &str = "/dev/tty";
+
+/// Lines kept below the table for the totals and the key help.
+const FOOTER_LINES: usize = 4;
+
+#[derive(Debug, Clone)]
+pub enum State {
+    Pending,
+    Parsing(Instant),
+    Solving(Instant),
+    Done(SolveResult),
+}
+
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    First,
+    Last,
+    Quit,
+}
+
+/// Puts the terminal into raw mode on an alternate screen, and back when dropped.
+struct Terminal {
+    saved: String,
+}
+
+fn stty(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open(TTY).map_err(|err| format!("{}: {}", TTY, err))?)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("could not run stty: {}", err))?;
+
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => Err(format!("stty {} failed", args.join(" "))),
+    }
+}
+
+impl Terminal {
+    fn enter() -> Result<Self, String> {
+        let saved = stty(&["-g"])?;
+
+        // Reads return after at most 100ms, so the screen keeps updating without keys.
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "1"])?;
+
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush().expect("flush stdout");
+
+        Ok(Terminal { saved })
+    }
+
+    fn size() -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (height, width) = size.split_once(' ')?;
+                Some((width.parse().ok()?, height.parse().ok()?))
+            })
+            .unwrap_or((PRINT_WIDTH, 24))
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        _ = io::stdout().flush();
+        _ = stty(&[&self.saved]);
+    }
+}
+
+fn keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let key = match &bytes[index..] {
+            [0x1b, b'[', b'A', ..] => Some(Key::Up),
+            [0x1b, b'[', b'B', ..] => Some(Key::Down),
+            [b'k', ..] => Some(Key::Up),
+            [b'j', ..] => Some(Key::Down),
+            [b'g', ..] => Some(Key::First),
+            [b'G', ..] => Some(Key::Last),
+            // Ctrl-C arrives as a byte without the signal.
+            [b'q' | 0x03, ..] => Some(Key::Quit),
+            _ => None,
+        };
+
+        index += match &bytes[index..] {
+            [0x1b, b'[', _, ..] => 3,
+            _ => 1,
+        };
+
+        keys.extend(key);
+    }
+
+    keys
+}
+
+/// Runs the jobs while showing a table of the parts that fills in as they finish. The table
+/// stays up once everything is done, until it is closed with `q`.
+///
+/// Returns `None` when closed before the run finished. The solutions can't be stopped, so
+/// they keep running in the background until the process exits.
+pub fn dashboard(
+    jobs: &[Job],
+    inputs: &Arc<Inputs>,
+    settings: &Settings,
+    answers: &Answers,
+) -> Result<Option<Run>, String> {
+    if !io::stdout().is_terminal() {
+        return Err("--tui needs a terminal".to_string());
+    }
+
+    let mut tty = File::open(TTY).map_err(|err| format!("{}: {}", TTY, err))?;
+
+    let mut rows: Vec<Row> = jobs
+        .iter()
+        .map(|job| Row {
+            day: job.solution.day(),
+            part: job.part,
+            name: job.solution.name(),
+            state: State::Pending,
+        })
+        .collect();
+
+    let (sender, receiver) = mpsc::channel();
+    let frames = Frames::default();
+    // Other sinks are rejected by the command line, printed ones would end up all over the table.
+    let settings = Settings {
+        progress: Some(sender),
+        visuals: Visuals {
+            sink: Sink::Buffer(frames.clone()),
+            ..settings.visuals.clone()
+        },
+        ..settings.clone()
+    };
+
+    let _terminal = Terminal::enter()?;
+    let start = Instant::now();
+
+    let runner = {
+        let jobs = jobs.to_vec();
+        let inputs = inputs.clone();
+        thread::spawn(move || runner::run(&jobs, &inputs, &settings))
+    };
+
+    let mut runner = Some(runner);
+    let mut run = None;
+    let mut selected = 0;
+    let mut size = Terminal::size();
+    let mut buffer = [0; 64];
+
+    for frame in 0.. {
+        for progress in receiver.try_iter() {
+            update(&mut rows, progress, answers);
+        }
+
+        if runner.as_ref().is_some_and(|handle| handle.is_finished()) {
+            let handle = runner.take().expect("runner checked above");
+            run = Some(handle.join().expect("runner doesn't panic"));
+        }
+
+        if frame % 10 == 0 {
+            size = Terminal::size();
+        }
+
+        let elapsed = match &run {
+            Some(run) => run.real_time,
+            None => start.elapsed(),
+        };
+
+        draw(&render(
+            &rows,
+            &frames,
+            selected,
+            elapsed,
+            Instant::now(),
+            size,
+        ));
+
+        let read = tty.read(&mut buffer).unwrap_or(0);
+
+        for key in keys(&buffer[..read]) {
+            match key {
+                Key::Up => selected = selected.saturating_sub(1),
+                Key::Down => selected = (selected + 1).min(rows.len().saturating_sub(1)),
+                Key::First => selected = 0,
+                Key::Last => selected = rows.len().saturating_sub(1),
+                Key::Quit => return Ok(run),
+            }
+        }
+    }
+
+    unreachable!("the frames never run out")
+}
+
+fn draw(screen: &[String]) {
+    let mut stdout = io::stdout().lock();
+
+    write!(stdout, "\x1b[H").unwrap();
+    for line in screen {
+        writeln!(stdout, "{}\x1b[K", line).unwrap();
+    }
+    write!(stdout, "\x1b[J").unwrap();
+
+    stdout.flush().unwrap();
+}
+
+fn update(rows: &mut [Row], progress: Progress, answers: &Answers) {
+    let now = Instant::now();
+
+    for row in rows {
+        match &progress {
+            Progress::Parsing { day } if row.day == *day => {
+                if matches!(row.state, State::Pending) {
+                    row.state = State::Parsing(now);
+                }
+            }
+            Progress::Solving { day, part } if row.day == *day && row.part == *part => {
+                row.state = State::Solving(now);
+            }
+            Progress::Finished(result) if row.day == result.day && row.part == result.part => {
+                let mut result = result.clone();
+                answers.verify(&mut result);
+                row.state = State::Done(result);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn color(state: &State) -> &'static str {
+    match state {
+        State::Pending => "2",
+        State::Parsing(_) | State::Solving(_) => "34",
+        State::Done(result) => match result.status {
+            Status::Pass => "32",
+            Status::Fail(_) | Status::Panicked(_) | Status::Timeout(_) => "31",
+            Status::Todo | Status::NoInput(_) => "33",
+            Status::Unknown | Status::Cached => "36",
+        },
+    }
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+fn row_line(row: &Row, now: Instant) -> String {
+    let prefix = format!("day{}pt{}: ", row.day, row.part);
+
+    let (status, content, duration) = match &row.state {
+        State::Pending => return format!("{}{:>w$}", prefix, "PENDING", w = PRINT_WIDTH - 9),
+        State::Parsing(since) => ("PARSING".to_string(), String::new(), now - *since),
+        State::Solving(since) => ("SOLVING".to_string(), String::new(), now - *since),
+        State::Done(result) => {
+            let content = match report::row_content(result) {
+                content if content.contains('\n') => "(multi-line, select to show)".to_string(),
+                content => content,
+            };
+
+            (result.status.to_string(), content, result.solve_time)
+        }
+    };
+
+    report::display_with_duration(
+        PRINT_WIDTH,
+        TIME_PAD_WIDTH,
+        false,
+        &prefix,
+        &format!("{:<8} -", status),
+        &content,
+        duration,
+    )
+}
+
+/// The full answer and visualization of the selected row.
+fn details(row: &Row, frames: &Frames) -> Vec<String> {
+    let mut lines = vec![format!("day{} part {}: {}", row.day, row.part, row.name)];
+
+    match &row.state {
+        State::Done(result) => {
+            lines.extend(report::row_content(result).lines().map(String::from));
+        }
+        State::Pending => lines.push("waiting".to_string()),
+        State::Parsing(_) => lines.push("parsing the input".to_string()),
+        State::Solving(_) => lines.push("solving".to_string()),
+    }
+
+    if let Some(visualization) = frames.get(row.day, row.part).pop() {
+        lines.push(String::new());
+        lines.push("visualization:".to_string());
+        lines.extend(visualization.lines().map(String::from));
+    }
+
+    lines
+}
+
+/// The lines of one screen of the dashboard, sized to `width` by `height` characters.
+pub fn render(
+    rows: &[Row],
+    frames: &Frames,
+    selected: usize,
+    elapsed: Duration,
+    now: Instant,
+    (width, height): (usize, usize),
+) -> Vec<String> {
+    // Leave at least half of the screen for the details when the table doesn't fit.
+    let table_height = match rows.len() + FOOTER_LINES <= height / 2 {
+        true => rows.len(),
+        false => (height.saturating_sub(FOOTER_LINES) / 2).max(1),
+    };
+    let first = selected.saturating_sub(table_height.saturating_sub(1));
+
+    let mut lines: Vec<String> = rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(table_height)
+        .map(|(index, row)| {
+            let line = truncate(&row_line(row, now), width);
+            match index == selected {
+                true => format!("\x1b[{};7m{:<width$}\x1b[0m", color(&row.state), line),
+                false => format!("\x1b[{}m{}\x1b[0m", color(&row.state), line),
+            }
+        })
+        .collect();
+
+    let done: Vec<&SolveResult> = rows
+        .iter()
+        .filter_map(|row| match &row.state {
+            State::Done(result) => Some(result),
+            _ => None,
+        })
+        .collect();
+
+    let running = rows
+        .iter()
+        .filter(|row| matches!(row.state, State::Parsing(_) | State::Solving(_)))
+        .count();
+
+    // Parts of a day share the parse, count it once like the text report.
+    let parse_time: Duration = done
+        .iter()
+        .map(|result| (result.day, result.parse_time))
+        .collect::<BTreeMap<_, _>>()
+        .values()
+        .sum();
+    let thread_time = parse_time
+        + done
+            .iter()
+            .map(|result| result.solve_time)
+            .sum::<Duration>();
+
+    lines.push(String::new());
+    lines.push(truncate(
+        &report::display_with_duration(
+            PRINT_WIDTH,
+            TIME_PAD_WIDTH,
+            false,
+            "> ",
+            "-",
+            &format!(
+                "{}/{} done, {} running, thread time",
+                done.len(),
+                rows.len(),
+                running
+            ),
+            thread_time,
+        ),
+        width,
+    ));
+    lines.push(truncate(
+        &report::display_with_duration(
+            PRINT_WIDTH,
+            TIME_PAD_WIDTH,
+            false,
+            "> ",
+            "-",
+            "real time",
+            elapsed,
+        ),
+        width,
+    ));
+    lines.push(
+        "\x1b[2m".to_string() + &truncate("j/k or arrows: select, q: quit", width) + "\x1b[0m",
+    );
+
+    if let Some(row) = rows.get(selected) {
+        lines.push(String::new());
+        lines.extend(
+            details(row, frames)
+                .iter()
+                .map(|line| truncate(line, width)),
+        );
+    }
+
+    lines.truncate(height);
+    lines
+}
+
+#[test]
+fn renders_dashboard() {
+    use crate::answer::Answer;
+
+    let now = Instant::now();
+    let result = SolveResult::fixture(
+        10,
+        2,
+        Some(Answer::bitmap("#..#\n.##.")),
+        Status::Pass,
+        Duration::from_millis(2),
+        Duration::from_millis(3),
+    );
+
+    let rows = vec![
+        Row {
+            day: 10,
+            part: 2,
+            name: result.name,
+            state: State::Done(result.clone()),
+        },
+        Row {
+            day: 11,
+            part: 1,
+            name: "Monkey in the Middle",
+            state: State::Solving(now - Duration::from_millis(5)),
+        },
+        Row {
+            day: 11,
+            part: 2,
+            name: "Monkey in the Middle",
+            state: State::Pending,
+        },
+        Row {
+            day: 10,
+            part: 1,
+            name: result.name,
+            state: State::Done(SolveResult {
+                part: 1,
+                ..result.clone()
+            }),
+        },
+    ];
+
+    let frames = Frames::default();
+    let lines = render(&rows, &frames, 0, Duration::from_millis(8), now, (100, 40));
+
+    assert!(lines[0].starts_with("\x1b[32;7mday10pt2: (multi-line"));
+    assert!(lines[0].contains("PASS"));
+    assert!(lines[1].contains("SOLVING"));
+    assert!(lines[1].ends_with("5ms\x1b[0m"));
+    assert!(lines[2].contains("PENDING"));
+    // The parse of day 10 counts once.
+    assert!(lines[5].contains("2/4 done, 1 running"));
+    assert!(lines[5].ends_with("8ms"));
+    assert!(lines.iter().any(|line| line == "#..#"));
+
+    let lines = render(&rows, &frames, 2, Duration::ZERO, now, (20, 6));
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].contains("day11pt2"));
+
+    assert_eq!(
+        keys(b"jk\x1b[A\x1b[Bxq"),
+        vec![Key::Down, Key::Up, Key::Up, Key::Down, Key::Quit]
+    );
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{answer::Answer, cli::Options, examples::Example, input::InputSource, json::Value};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Editors often write a file in several steps, wait for them to finish.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub answer: String,
+    pub status: String,
+    pub time: Duration,
+}
+
+pub type Outcomes = BTreeMap<u8, Outcome>;
+
+/// The crate being watched, so that watching works from any directory.
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn example_dir() -> PathBuf {
+    manifest_dir().join("example_input")
+}
+
+/// Every file below `dir`, in no particular order.
+fn files_below(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        match path.is_dir() {
+            true => files_below(&path, files),
+            false => files.push(path),
+        }
+    }
+}
+
+/// The whole source, since a day depends on shared modules too, and the files of `day`.
+/// Listed anew on every check, so that added and removed files count as changes.
+fn watched_paths(day: u8, options: &Options) -> Vec<PathBuf> {
+    let mut paths = vec![
+        manifest_dir().join("Cargo.toml"),
+        example_dir().join(format!("day{}.txt", day)),
+        example_dir().join(format!("day{}.expected", day)),
+    ];
+
+    files_below(&manifest_dir().join("src"), &mut paths);
+
+    match &options.input {
+        InputSource::Directory(dir) => paths.push(dir.join(format!("day{}.txt", day))),
+        InputSource::File(path) => paths.push(path.clone()),
+        _ => {}
+    }
+
+    paths
+}
+
+fn modified(paths: Vec<PathBuf>) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = path
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Re-runs `day` on the example and the real input whenever one of its files changes. The
+/// solutions are rebuilt with cargo, so changes to the source are picked up.
+pub fn watch(day: u8, options: &Options) {
+    let mut seen = modified(watched_paths(day, options));
+    let mut previous: BTreeMap<&str, Outcomes> = BTreeMap::new();
+    let mut reason = "first run".to_string();
+
+    loop {
+        println!("[day{}] {}", day, reason);
+
+        let example_path = example_dir().join(format!("day{}.expected", day));
+        let example = match example_path.exists() {
+            true => Some(Example::load(&example_path)),
+            false => None,
+        };
+
+        let mut runs = vec![];
+
+        match example {
+            Some(Ok(example)) => runs.push(("example", Some(example))),
+            Some(Err(err)) => println!("error: {}", err),
+            None if example_dir().join(format!("day{}.txt", day)).exists() => {
+                runs.push(("example", Some(Example::default())))
+            }
+            None => {}
+        }
+
+        runs.push(("input", None));
+
+        for (label, example) in runs {
+            match solve(day, options, example.as_ref()) {
+                Ok(outcomes) => {
+                    let expected = example.as_ref().map(|example| &example.expected);
+                    print!(
+                        "{}",
+                        render(label, &outcomes, previous.get(label), expected)
+                    );
+                    previous.insert(label, outcomes);
+                }
+                Err(err) => println!("{:<9}{}", label, err),
+            }
+        }
+
+        println!();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            if modified(watched_paths(day, options)) != seen {
+                thread::sleep(SETTLE_TIME);
+                break;
+            }
+        }
+
+        let current = modified(watched_paths(day, options));
+
+        reason = seen
+            .keys()
+            .chain(current.keys().filter(|path| !seen.contains_key(*path)))
+            .filter(|path| seen.get(*path) != current.get(*path))
+            .map(|path| {
+                path.strip_prefix(manifest_dir())
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+            + " changed";
+
+        seen = current;
+    }
+}
+
+/// Solves the day in a fresh build of the binary and reads its JSON report.
+fn solve(day: u8, options: &Options, example: Option<&Example>) -> Result<Outcomes, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut command = Command::new(cargo);
+    command
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(manifest_dir().join("Cargo.toml"));
+
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command.args(["--", "run", &day.to_string(), "--format", "json"]);
+
+    if let [part] = options.parts.as_slice() {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    if let Some(timeout) = options.timeout {
+        command.args(["--timeout", &timeout.as_secs_f64().to_string()]);
+    }
+
+    match example {
+        Some(example) => {
+            let input = example
+                .input
+                .clone()
+                .unwrap_or_else(|| format!("day{}.txt", day));
+            command.arg("--input").arg(example_dir().join(input));
+
+            for (key, value) in &example.params {
+                command.args(["--param", &format!("{}={}", key, value)]);
+            }
+        }
+        None => match &options.input {
+            InputSource::Directory(dir) => _ = command.arg("--input-dir").arg(dir),
+            InputSource::File(path) => _ = command.arg("--input").arg(path),
+            _ => {}
+        },
+    }
+
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("could not run cargo: {}", err))?;
+
+    if output.stdout.is_empty() {
+        return Err(format!("no report, cargo exited with {}", output.status));
+    }
+
+    let report = Value::try_from(String::from_utf8_lossy(&output.stdout).as_ref())?;
+
+    let mut outcomes = Outcomes::new();
+
+    for result in report
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or("missing results in report")?
+    {
+        let text = |key: &str| result.get(key).and_then(Value::as_str).unwrap_or_default();
+
+        let Some(part) = result.get("part").and_then(Value::as_u64) else {
+            continue;
+        };
+
+        let answer = match (result.get("message"), result.get("answer")) {
+            (Some(message), _) => message.as_str().unwrap_or_default().to_string(),
+            (None, Some(answer)) => answer_text(answer),
+            (None, None) => String::new(),
+        };
+
+        let time = result
+            .get("solve_time_ns")
+            .and_then(Value::as_u64)
+            .map(Duration::from_nanos)
+            .unwrap_or_default();
+
+        outcomes.insert(
+            part as u8,
+            Outcome {
+                answer,
+                status: text("status").to_string(),
+                time,
+            },
+        );
+    }
+
+    Ok(outcomes)
+}
+
+/// The displayed answer from the JSON report, where integers are numbers and bitmaps are
+/// arrays of rows.
+fn answer_text(answer: &Value) -> String {
+    match answer {
+        Value::Number(number) => number.clone(),
+        Value::String(text) => text.clone(),
+        Value::Array(rows) => rows
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// One row per part with the change since the previous run. Multi-line answers that changed
+/// are printed in full below their row.
+pub fn render(
+    label: &str,
+    outcomes: &Outcomes,
+    previous: Option<&Outcomes>,
+    expected: Option<&BTreeMap<u8, Answer>>,
+) -> String {
+    let mut output = String::new();
+
+    for (part, outcome) in outcomes {
+        let status = match expected.and_then(|expected| expected.get(part)) {
+            Some(expected) if outcome.status == "UNKNOWN" => {
+                match Answer::parse(&outcome.answer).matches(expected) {
+                    true => "PASS",
+                    false => "FAIL",
+                }
+            }
+            _ => outcome.status.as_str(),
+        };
+
+        let before = previous.and_then(|previous| previous.get(part));
+
+        let change = match before {
+            None => String::new(),
+            Some(before) if before.answer == outcome.answer => "unchanged".to_string(),
+            Some(_) if outcome.answer.contains('\n') => "changed".to_string(),
+            Some(before) => format!("was {}", before.answer),
+        };
+
+        let answer = match outcome.answer.contains('\n') {
+            true => "(multi-line)",
+            false => outcome.answer.as_str(),
+        };
+
+        let row = format!(
+            "{:<9}pt{}  {:<20} {:<9}{:>15}  {}",
+            label,
+            part,
+            answer,
+            status,
+            format!("{:?}", outcome.time),
+            change
+        );
+
+        writeln!(output, "{}", row.trim_end()).unwrap();
+
+        if outcome.answer.contains('\n') && change != "unchanged" {
+            writeln!(output, "{}", outcome.answer.trim_end()).unwrap();
+        }
+    }
+
+    output
+}
+
+#[test]
+fn renders_answer_changes() {
+    let outcome = |answer: &str| Outcome {
+        answer: answer.to_string(),
+        status: "UNKNOWN".to_string(),
+        time: Duration::from_millis(2),
+    };
+
+    let before = Outcomes::from([(1, outcome("3068")), (2, outcome("#.\n.#"))]);
+    let after = Outcomes::from([(1, outcome("3069")), (2, outcome("#.\n.#"))]);
+    let expected = BTreeMap::from([(1, Answer::Integer(3068))]);
+
+    let output = render("example", &after, Some(&before), Some(&expected));
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("example  pt1  3069"));
+    assert!(lines[0].contains("FAIL"));
+    assert!(lines[0].ends_with("was 3068"));
+    assert!(lines[1].contains("(multi-line)"));
+    assert!(lines[1].ends_with("unchanged"));
+
+    let output = render("input", &before, None, None);
+    assert!(output.contains("UNKNOWN"));
+    assert!(output.contains("#.\n.#"));
+}
+
+#[test]
+fn watches_the_whole_source() {
+    let paths = watched_paths(17, &Options::default());
+
+    for file in [
+        "src/day17.rs",
+        "src/solution.rs",
+        "src/extra.rs",
+        "Cargo.toml",
+    ] {
+        assert!(paths.contains(&manifest_dir().join(file)), "{}", file);
+    }
+    assert!(paths.contains(&example_dir().join("day17.expected")));
+}
@@ -1,8 +1,14 @@
 #!/bin/env bash
 
 set -e
-date -u +"# Advent of Code %Y - Day %d" > release.md
-echo >> release.md
-echo "\`\`\`plaintext" >> release.md
-./target/release/aoc2022 >> release.md
-echo "\`\`\`" >> release.md
\ No newline at end of file
+
+# Failing and unfinished days exit with 1, the reports show them and are published
+# regardless. Any other error means the run itself broke.
+status=0
+./target/release/aoc2022 run --timeout 60 \
+    --title "$(date -u +"Advent of Code %Y - Day %d")" \
+    --output release.md --output release.html || status=$?
+
+if [ "$status" -gt 1 ]; then
+    exit "$status"
+fi